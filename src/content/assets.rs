@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use avian3d::prelude::*;
 
 pub struct AssetLoadingPlugin;
 
@@ -6,13 +7,9 @@ impl Plugin for AssetLoadingPlugin {
     fn build(&self, app: &mut App) {
         app
             .init_resource::<GameAssets>()
-            .init_resource::<WeaponArm>()
             .add_event::<AssetLoadingComplete>()
             .add_systems(Startup, load_game_assets)
-            .add_systems(Update, (
-                on_gltf_ready,
-                check_asset_loading
-            ));
+            .add_systems(Update, check_asset_loading);
 
     }
 }
@@ -25,16 +22,6 @@ pub struct GameAssets {
     pub loading_progress: f32,
 }
 
-#[derive(Resource, Default)]
-pub struct WeaponArm {
-    pub scene: Handle<Scene>,
-    pub idle: Handle<AnimationClip>,
-    pub walk: Handle<AnimationClip>,
-    pub fire: Handle<AnimationClip>,
-    pub reload: Handle<AnimationClip>,
-    pub reload_fast: Handle<AnimationClip>,
-}
-
 #[derive(Component)]
 pub struct LoadingScreen;
 
@@ -100,55 +87,6 @@ fn check_asset_loading(
 pub struct AssetLoadingComplete;
 
 
-fn on_gltf_ready(
-    mut ev: EventReader<AssetLoadingComplete>,
-    gltfs: Res<Assets<Gltf>>,
-    game_assets: Res<GameAssets>,
-    mut weapon_arm_res: ResMut<WeaponArm>,
-) {
-    // wenn das event nicht getriggert wurde. einfach hier beenden
-    if ev.is_empty() { return; }
-
-    let Some(gltf) = gltfs.get(&game_assets.weapon_asset) else { return; };
-    ev.clear(); // event wurde getriggert, also löschen damit das nicht nochmal kommt
-
-    let scene = gltf
-        .named_scenes["Sketchfab_Scene"]
-        .clone();
-
-    let idle = gltf.named_animations.get("Armature|Saiga_Idle")
-        .cloned()
-        .or_else(|| gltf.animations.get(0).cloned())
-        .expect("Keine Idle-Animation gefunden");
-
-    let walk  = gltf.named_animations.get("Armature|Saiga_Walk").cloned()
-        .or_else(|| gltf.named_animations.get("Run").cloned())
-        .or_else(|| gltf.animations.get(1).cloned())
-        .expect("Keine Walk-Animation gefunden");
-
-    let fire = gltf.named_animations.get("Armature|Saiga_Fire").cloned()
-        .or_else(|| gltf.animations.get(2).cloned())
-        .expect("Keine Fire-Animation gefunden");
-    
-    let reload_fast = gltf.named_animations.get("Armature|Saiga_Reload_Fast").cloned()
-        .or_else(|| gltf.animations.get(3).cloned())
-        .expect("Keine Reload_Fast-Animation gefunden");
-
-    let reload = gltf.named_animations.get("Armature|Saiga_Reload_Full").cloned()
-        .or_else(|| gltf.animations.get(3).cloned())
-        .expect("Keine Reload-Animation gefunden");
-
-    *weapon_arm_res = WeaponArm { 
-        scene, 
-        idle,
-        walk,
-        fire,
-        reload_fast,
-        reload
-    };
-    //game_assets.assets_loaded = true;
-}
-
 /*pub fn spawn_weapon_model(
     commands: &mut Commands,
     game_assets: &GameAssets,
@@ -169,13 +107,19 @@ pub fn spawn_character_model(
 ) -> Entity {
     // For now, create a simple character representation
     // In a full implementation, this would load the actual FBX character
+    // Ursprung liegt auf Beckenhöhe, passend zum Ragdoll-Profil
     commands.spawn((
-        Mesh3d(meshes.add(Capsule3d::new(0.4, 1.8))),
+        Mesh3d(meshes.add(Capsule3d::new(0.3, 1.5))),
         MeshMaterial3d(materials.add(StandardMaterial {
             base_color: Color::srgb(0.8, 0.6, 0.4), // Skin-like color
             ..default()
         })),
         Transform::from_translation(position),
+        RigidBody::Static,
+        Collider::capsule(0.3, 1.5),
+        crate::core::physics::GameLayer::Player.layers(),
+        crate::gameplay::damage::Health::default(),
+        crate::gameplay::ragdoll::RagdollProfile::humanoid(),
         CharacterModel,
     )).id()
 }
@@ -1,4 +1,12 @@
 use bevy::prelude::*;
+use avian3d::prelude::*;
+
+use crate::core::physics::GameLayer;
+use crate::gameplay::explosions::EnvExplosion;
+use crate::gameplay::interaction;
+use crate::gameplay::props;
+use crate::gameplay::weapons::AmmoType;
+use crate::gameplay::surfaces::SurfaceTexture;
 
 pub struct MapLoadingPlugin;
 
@@ -107,7 +115,22 @@ pub fn spawn_map_geometry(
     // Convert BSP brushes to Bevy meshes
     for brush in &map.brushes {
         if brush.is_solid {
-            // Create collision geometry
+            // Create collision geometry, the texture decides the surface material
+            let Some(collider) = Collider::convex_hull(brush.vertices.clone()) else {
+                warn!("Skipping degenerate brush with texture {}", brush.texture);
+                continue;
+            };
+
+            commands.spawn((
+                Transform::default(),
+                RigidBody::Static,
+                collider,
+                GameLayer::World.layers(),
+                crate::core::physics::StaticCollider,
+                SurfaceTexture(brush.texture.clone()),
+                MapGeometry,
+                MapEntity,
+            ));
         } else {
             // Create visual geometry
         }
@@ -116,6 +139,8 @@ pub fn spawn_map_geometry(
 
 pub fn spawn_map_entities(
     commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
     map: &BspMap,
 ) {
     for entity in &map.entities {
@@ -144,6 +169,52 @@ pub fn spawn_map_entities(
                     }
                 }
             },
+            "func_breakable" | "func_pushable" => {
+                let settings = props::prop_settings_from_map(&entity.classname, &entity.properties);
+                let prop = props::spawn_prop(
+                    commands,
+                    meshes,
+                    materials,
+                    Transform::from_translation(entity.origin),
+                    &settings,
+                );
+                commands.entity(prop).insert(MapEntity);
+            },
+            "item_battery" | "item_kevlar" => {
+                let armor = if entity.classname == "item_kevlar" {
+                    interaction::KEVLAR_ARMOR
+                } else {
+                    interaction::BATTERY_ARMOR
+                };
+                interaction::spawn_armor_pack(commands, meshes, materials, entity.origin, armor);
+            },
+            classname if classname.starts_with("ammo_") || classname.starts_with("item_ammo_") => {
+                match AmmoType::from_classname(classname) {
+                    Some((ammo_type, amount)) => {
+                        interaction::spawn_ammo_pack(commands, meshes, materials, entity.origin, ammo_type, amount);
+                    },
+                    None => info!("Unknown ammo type: {}", classname),
+                }
+            },
+            "func_button" => {
+                interaction::spawn_button(commands, meshes, materials, entity.origin, entity.properties.get("target").cloned());
+            },
+            "env_explosion" => {
+                // Mit targetname wartet sie auf ein FireTarget; zielt nichts darauf, geht sie nie los
+                if let Some(targetname) = entity.properties.get("targetname") {
+                    let targeted = map.entities
+                        .iter()
+                        .any(|other| other.properties.get("target") == Some(targetname));
+                    if !targeted {
+                        warn!("env_explosion '{}' is never triggered: no entity targets it", targetname);
+                    }
+                }
+                commands.spawn((
+                    Transform::from_translation(entity.origin),
+                    EnvExplosion::from_map(&entity.properties),
+                    MapEntity,
+                ));
+            },
             _ => {
                 // Handle other entity types
                 info!("Unknown entity type: {}", entity.classname);
@@ -3,9 +3,12 @@
 //! Contains content loading and management:
 //! - Asset loading and management
 //! - Map loading and BSP support
+//! - Data-driven weapon definitions
 
 pub mod assets;
 pub mod maps;
+pub mod weapon_definitions;
 
 pub use assets::AssetLoadingPlugin;
-pub use maps::MapLoadingPlugin;
\ No newline at end of file
+pub use maps::MapLoadingPlugin;
+pub use weapon_definitions::WeaponDefinitionPlugin;
\ No newline at end of file
@@ -0,0 +1,505 @@
+//! Weapon definition assets
+//!
+//! Every `assets/weapons/<id>.weapon.ron` file describes one weapon: stats,
+//! ammo, spread, recoil, the viewmodel glTF and the names of its animation
+//! clips. All files in the folder are loaded at startup and registered under
+//! their file name, so adding a weapon needs no code changes. Systems always
+//! read the current asset, which makes hot reloading work out of the box.
+
+use std::collections::HashMap;
+
+use bevy::asset::{io::Reader, AssetLoader, LoadContext, LoadedFolder};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::gameplay::projectiles::ProjectileKind;
+use crate::gameplay::weapons::AmmoType;
+
+/// Folder (below `assets/`) that holds the weapon definitions
+pub const WEAPONS_FOLDER: &str = "weapons";
+
+pub struct WeaponDefinitionPlugin;
+
+impl Plugin for WeaponDefinitionPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_asset::<WeaponDefinition>()
+            .init_asset_loader::<WeaponDefinitionLoader>()
+            .init_resource::<WeaponDefinitions>()
+            .add_systems(Startup, load_weapon_definitions)
+            .add_systems(Update, (
+                register_weapon_definitions,
+                log_weapon_definition_changes,
+            ));
+    }
+}
+
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct WeaponDefinition {
+    /// File name without extension, filled in by the loader
+    #[serde(skip)]
+    pub id: String,
+    pub name: String,
+    /// HL-style bucket, selected with the number keys
+    #[serde(default = "default_slot")]
+    pub slot: u8,
+    /// Order inside the bucket
+    #[serde(default)]
+    pub slot_position: u8,
+    /// Seconds to put the weapon away when switching
+    #[serde(default = "default_holster_time")]
+    pub holster_time: f32,
+    /// Seconds after drawing before the weapon can fire
+    #[serde(default = "default_draw_time")]
+    pub draw_time: f32,
+    /// Damage per pellet
+    pub damage: f32,
+    /// Rays traced per shot, each with its own spot in the spread cone
+    #[serde(default = "default_pellets")]
+    pub pellets: u32,
+    #[serde(default)]
+    pub falloff: DamageFalloff,
+    #[serde(default)]
+    pub penetration: WeaponPenetration,
+    /// Seconds between two shots
+    pub fire_rate: f32,
+    /// Modes cycled with the fire mode key, the first one is active on pickup
+    #[serde(default = "default_fire_modes")]
+    pub fire_modes: Vec<FireMode>,
+    pub range: f32,
+    #[serde(default)]
+    pub magazine_size: u32,
+    /// Ammo pool the weapon reloads from, `None` for weapons without ammo
+    #[serde(default)]
+    pub ammo_type: Option<AmmoType>,
+    #[serde(default)]
+    pub reload_mode: ReloadMode,
+    /// Seconds per reload when the viewmodel clip length is unknown,
+    /// for [`ReloadMode::Shell`] the time per shell
+    #[serde(default = "default_reload_time")]
+    pub reload_time: f32,
+    #[serde(default)]
+    pub spread: WeaponSpread,
+    #[serde(default)]
+    pub recoil: WeaponRecoil,
+    /// Right mouse button aims down the sights or zooms the scope
+    #[serde(default)]
+    pub aim: Option<AimDefinition>,
+    /// Fire a physical projectile instead of tracing rays
+    #[serde(default)]
+    pub projectile: Option<ProjectileDefinition>,
+    /// Swing a hull instead of firing, `damage` and `fire_rate` are the primary attack
+    #[serde(default)]
+    pub melee: Option<MeleeDefinition>,
+    /// Path of the viewmodel glTF, relative to `assets/`
+    pub viewmodel: String,
+    /// Named scene inside the glTF
+    pub viewmodel_scene: String,
+    /// Position of the viewmodel relative to the camera
+    #[serde(default = "default_viewmodel_offset")]
+    pub viewmodel_offset: [f32; 3],
+    pub animations: WeaponAnimationNames,
+    #[serde(default)]
+    pub pickup: WeaponPickupLook,
+    #[serde(skip)]
+    #[dependency]
+    pub viewmodel_gltf: Handle<Gltf>,
+}
+
+fn default_viewmodel_offset() -> [f32; 3] {
+    [0.2, -0.6, -0.2]
+}
+
+fn default_pellets() -> u32 {
+    1
+}
+
+fn default_fire_modes() -> Vec<FireMode> {
+    vec![FireMode::Auto]
+}
+
+fn default_reload_time() -> f32 {
+    2.0
+}
+
+fn default_slot() -> u8 {
+    1
+}
+
+fn default_holster_time() -> f32 {
+    0.3
+}
+
+fn default_draw_time() -> f32 {
+    0.5
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReloadMode {
+    /// The whole magazine is swapped once the reload animation ends
+    #[default]
+    Magazine,
+    /// One shell per `reload_time`, firing interrupts the reload
+    Shell,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FireMode {
+    /// One shot per click
+    Semi,
+    /// That many shots per click, `fire_rate` apart
+    Burst(u32),
+    /// Fires as long as the button is held
+    Auto,
+}
+
+impl FireMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Semi => "Semi",
+            Self::Burst(_) => "Burst",
+            Self::Auto => "Auto",
+        }
+    }
+}
+
+/// Aiming down the sights: narrower view, slower mouse, tighter spread
+#[derive(Deserialize, Debug, Clone)]
+pub struct AimDefinition {
+    /// Vertical field of view in degrees when fully aimed
+    pub fov: f32,
+    /// Mouse sensitivity multiplier when fully aimed
+    #[serde(default = "default_aim_sensitivity")]
+    pub sensitivity: f32,
+    /// Spread multiplier when fully aimed
+    #[serde(default = "default_aim_spread_scale")]
+    pub spread_scale: f32,
+    /// Seconds to raise the sights
+    #[serde(default = "default_aim_time")]
+    pub time: f32,
+    /// Scope overlay instead of the viewmodel
+    #[serde(default)]
+    pub scope: bool,
+}
+
+fn default_aim_sensitivity() -> f32 {
+    0.7
+}
+
+fn default_aim_spread_scale() -> f32 {
+    0.5
+}
+
+fn default_aim_time() -> f32 {
+    0.2
+}
+
+/// Damage drop over distance: full damage up to `start` meters, then
+/// linearly down to `min_scale` of it at the weapon's range
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(default)]
+pub struct DamageFalloff {
+    pub start: f32,
+    pub min_scale: f32,
+}
+
+impl Default for DamageFalloff {
+    fn default() -> Self {
+        Self {
+            start: 0.0,
+            min_scale: 1.0,
+        }
+    }
+}
+
+/// CS-style wallbangs: each surface costs its thickness in meters times its
+/// [`SurfaceProperties::density`](crate::gameplay::surfaces::SurfaceProperties)
+/// from `power`, the ray stops once that runs out
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(default)]
+pub struct WeaponPenetration {
+    pub power: f32,
+    /// Damage multiplier after each layer
+    pub damage_scale: f32,
+    /// Most layers one ray goes through
+    pub max_layers: u32,
+}
+
+impl Default for WeaponPenetration {
+    fn default() -> Self {
+        Self {
+            power: 0.0,
+            damage_scale: 0.5,
+            max_layers: 2,
+        }
+    }
+}
+
+impl WeaponDefinition {
+    /// Damage of one pellet hitting at `distance`
+    pub fn damage_at(&self, distance: f32) -> f32 {
+        let span = (self.range - self.falloff.start).max(f32::EPSILON);
+        let t = ((distance - self.falloff.start) / span).clamp(0.0, 1.0);
+        self.damage * (1.0 - t * (1.0 - self.falloff.min_scale))
+    }
+}
+
+/// Cone of fire, half angles in degrees; the current spread is the sum of
+/// the parts, capped at `max`
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct WeaponSpread {
+    pub base: f32,
+    /// Added at full running speed
+    pub moving: f32,
+    /// Added while airborne
+    pub air: f32,
+    /// Added per shot of sustained fire
+    pub per_shot: f32,
+    pub max: f32,
+    /// Degrees per second the sustained fire bloom shrinks
+    pub recovery: f32,
+}
+
+impl Default for WeaponSpread {
+    fn default() -> Self {
+        Self {
+            base: 0.5,
+            moving: 1.5,
+            air: 5.0,
+            per_shot: 0.5,
+            max: 8.0,
+            recovery: 6.0,
+        }
+    }
+}
+
+/// CS-style recoil: the n-th shot of a burst kicks the view by `pattern[n]`,
+/// past the end the last kick repeats with a random side
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct WeaponRecoil {
+    /// `[pitch up, yaw right]` in degrees per shot
+    pub pattern: Vec<[f32; 2]>,
+    /// Seconds without firing until the pattern starts over
+    pub reset_time: f32,
+    /// Degrees per second the view punch drifts back
+    pub recovery: f32,
+}
+
+impl Default for WeaponRecoil {
+    fn default() -> Self {
+        Self {
+            pattern: vec![[1.0, 0.0]],
+            reset_time: 0.4,
+            recovery: 10.0,
+        }
+    }
+}
+
+/// Launched body for grenade launchers, rockets and crossbows; `damage` of
+/// the weapon is the direct hit damage
+#[derive(Deserialize, Debug, Clone)]
+pub struct ProjectileDefinition {
+    pub kind: ProjectileKind,
+    /// Launch speed in m/s
+    pub speed: f32,
+    /// Collision radius in meters
+    pub radius: f32,
+    #[serde(default = "default_projectile_gravity")]
+    pub gravity: f32,
+    /// Explosion on detonation, 0 = none
+    #[serde(default)]
+    pub explosion_magnitude: f32,
+}
+
+fn default_projectile_gravity() -> f32 {
+    1.0
+}
+
+/// Crowbar-style close combat: a short box sweep along the view instead of a ray
+#[derive(Deserialize, Debug, Clone)]
+pub struct MeleeDefinition {
+    /// Size of the swept box in meters
+    #[serde(default = "default_melee_hull")]
+    pub hull: [f32; 3],
+    /// Damage of the secondary attack (right mouse button)
+    pub secondary_damage: f32,
+    /// Seconds between two secondary attacks
+    pub secondary_rate: f32,
+    /// Damage multiplier for hits in the back of the target
+    #[serde(default = "default_backstab_multiplier")]
+    pub backstab_multiplier: f32,
+}
+
+fn default_melee_hull() -> [f32; 3] {
+    [0.4, 0.4, 0.4]
+}
+
+fn default_backstab_multiplier() -> f32 {
+    3.0
+}
+
+/// Clip names inside the viewmodel glTF
+#[derive(Deserialize, Debug, Clone)]
+pub struct WeaponAnimationNames {
+    pub idle: String,
+    pub walk: String,
+    pub fire: String,
+    pub reload: String,
+    pub reload_fast: String,
+}
+
+/// Placeholder shape used for the weapon lying in the world
+#[derive(Deserialize, Debug, Clone)]
+pub struct WeaponPickupLook {
+    /// sRGB
+    pub color: [f32; 3],
+    pub size: [f32; 3],
+}
+
+impl Default for WeaponPickupLook {
+    fn default() -> Self {
+        Self {
+            color: [0.5, 0.5, 0.5],
+            size: [0.2, 0.2, 0.8],
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum WeaponDefinitionError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl std::fmt::Display for WeaponDefinitionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read weapon definition: {err}"),
+            Self::Ron(err) => write!(f, "invalid weapon definition: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for WeaponDefinitionError {}
+
+impl From<std::io::Error> for WeaponDefinitionError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for WeaponDefinitionError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        Self::Ron(err)
+    }
+}
+
+#[derive(Default)]
+pub struct WeaponDefinitionLoader;
+
+impl AssetLoader for WeaponDefinitionLoader {
+    type Asset = WeaponDefinition;
+    type Settings = ();
+    type Error = WeaponDefinitionError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<WeaponDefinition, WeaponDefinitionError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let mut definition: WeaponDefinition = ron::de::from_bytes(&bytes)?;
+        definition.id = weapon_id(load_context.path());
+        definition.viewmodel_gltf = load_context.load(definition.viewmodel.clone());
+
+        Ok(definition)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["weapon.ron"]
+    }
+}
+
+/// "weapons/pistol.weapon.ron" -> "pistol"
+fn weapon_id(path: &std::path::Path) -> String {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.split('.').next())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// All known weapon definitions by id
+#[derive(Resource, Default)]
+pub struct WeaponDefinitions {
+    folder: Handle<LoadedFolder>,
+    pub by_id: HashMap<String, Handle<WeaponDefinition>>,
+}
+
+impl WeaponDefinitions {
+    pub fn get(&self, id: &str) -> Option<&Handle<WeaponDefinition>> {
+        self.by_id.get(id)
+    }
+
+    /// Weapon ids in stable (alphabetical) order
+    pub fn ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self.by_id.keys().map(String::as_str).collect();
+        ids.sort_unstable();
+        ids
+    }
+}
+
+fn load_weapon_definitions(
+    asset_server: Res<AssetServer>,
+    mut definitions: ResMut<WeaponDefinitions>,
+) {
+    definitions.folder = asset_server.load_folder(WEAPONS_FOLDER);
+}
+
+fn register_weapon_definitions(
+    mut folder_events: EventReader<AssetEvent<LoadedFolder>>,
+    folders: Res<Assets<LoadedFolder>>,
+    mut definitions: ResMut<WeaponDefinitions>,
+) {
+    for event in folder_events.read() {
+        let AssetEvent::LoadedWithDependencies { id } = event else {
+            continue;
+        };
+        if *id != definitions.folder.id() {
+            continue;
+        }
+        let Some(folder) = folders.get(*id) else {
+            continue;
+        };
+
+        let loaded: Vec<(String, Handle<WeaponDefinition>)> = folder.handles
+            .iter()
+            .filter_map(|handle| {
+                let path = handle.path()?.path().to_path_buf();
+                let handle = handle.clone().try_typed::<WeaponDefinition>().ok()?;
+                Some((weapon_id(&path), handle))
+            })
+            .collect();
+
+        definitions.by_id.extend(loaded);
+        info!("Loaded {} weapon definitions: {:?}", definitions.by_id.len(), definitions.ids());
+    }
+}
+
+fn log_weapon_definition_changes(
+    mut events: EventReader<AssetEvent<WeaponDefinition>>,
+    assets: Res<Assets<WeaponDefinition>>,
+) {
+    for event in events.read() {
+        if let AssetEvent::Modified { id } = event {
+            if let Some(definition) = assets.get(*id) {
+                info!("Reloaded weapon definition '{}'", definition.id);
+            }
+        }
+    }
+}
@@ -2,16 +2,27 @@ use bevy::prelude::*;
 use bevy::input::mouse::MouseMotion;
 use avian3d::prelude::*;
 
+use crate::core::physics::{movement_filter, GameLayer};
+use crate::debug::cheats::Noclip;
+
 pub struct FpsControllerPlugin;
 
 impl Plugin for FpsControllerPlugin {
     fn build(&self, app: &mut App) {
         app
+            .register_type::<PlayerCmd>()
+            .register_type::<Lean>()
+            .add_event::<PlayerLanded>()
             .add_systems(Startup, setup_fps_controller)
+            // Input wird einmal pro Frame in ein PlayerCmd übersetzt, bevor FixedUpdate läuft
+            .add_systems(PreUpdate, sample_player_cmd.after(bevy::input::InputSystem))
+            .add_systems(Update, follow_lean_hitbox)
             .add_systems(FixedUpdate, (
-                fps_controller_look,       // kann auch in Update bleiben, aber hier ok
-                fps_controller_move,
-                check_grounded,
+                advance_cmd_tick,
+                fps_controller_look.after(advance_cmd_tick),
+                update_lean.after(fps_controller_look),
+                fps_controller_move.after(advance_cmd_tick),
+                check_grounded.after(advance_cmd_tick),
                 toggle_cursor_lock,        // kann in Update bleiben, funktioniert aber auch hier
             ));
     }
@@ -21,6 +32,8 @@ impl Plugin for FpsControllerPlugin {
 pub struct FpsController {
     pub speed: f32,
     pub sensitivity: f32,
+    /// Multiplier on `sensitivity`, lowered while aiming down the sights
+    pub sensitivity_scale: f32,
     pub enabled: bool,
     pub jump_height: f32,
     pub is_grounded: bool,
@@ -38,6 +51,8 @@ pub struct FpsController {
     pub ground_frames: u8,       // wie viele Frames hintereinander Boden
     pub max_air_jumps: u8,       // 0 = kein Double-Jump
     pub used_air_jumps: u8,      // Anzahl in aktueller Luftphase
+    pub prev_buttons: CmdButtons, // Buttons des zuletzt simulierten Commands (Flankenerkennung)
+    pub fall_speed: f32,          // höchste Fallgeschwindigkeit der aktuellen Luftphase
 
     // Timer intern:
     pub coyote_timer: f32,
@@ -49,6 +64,7 @@ impl Default for FpsController {
         Self {
             speed: 8.0,                 // Increased from 7.0 for more responsive movement
             sensitivity: 0.002,
+            sensitivity_scale: 1.0,
             enabled: true,
             jump_height: 1.8,           // Increased from 1.4 for higher jumps
             is_grounded: false,
@@ -65,6 +81,8 @@ impl Default for FpsController {
             ground_frames: 0,
             max_air_jumps: 0,
             used_air_jumps: 0,
+            prev_buttons: CmdButtons::NONE,
+            fall_speed: 0.0,
 
             coyote_timer: 0.0,
             jump_buffer_timer: -1.0,
@@ -72,6 +90,100 @@ impl Default for FpsController {
     }
 }
 
+/// Buttons held down in a single [`PlayerCmd`], stored as a bit set.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub struct CmdButtons(pub u32);
+
+impl CmdButtons {
+    pub const NONE: Self = Self(0);
+    pub const JUMP: Self = Self(1 << 0);
+    pub const FAST_FALL: Self = Self(1 << 1);
+    pub const ATTACK: Self = Self(1 << 2);
+    pub const ATTACK2: Self = Self(1 << 3);
+    pub const USE: Self = Self(1 << 4);
+    pub const DUCK: Self = Self(1 << 5);
+    pub const LEAN_LEFT: Self = Self(1 << 6);
+    pub const LEAN_RIGHT: Self = Self(1 << 7);
+    pub const RELOAD: Self = Self(1 << 8);
+
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn set(&mut self, other: Self, pressed: bool) {
+        if pressed {
+            self.0 |= other.0;
+        } else {
+            self.0 &= !other.0;
+        }
+    }
+}
+
+/// Per-tick player command, sampled from input once per frame.
+///
+/// Look and movement only read this command, never the input devices, so the
+/// same sequence of commands always produces the same simulation results. This
+/// is what replays, client-side prediction and bots build on.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+pub struct PlayerCmd {
+    /// Command number, incremented for every sampled command
+    pub tick: u32,
+    /// Absolute view yaw in radians
+    pub yaw: f32,
+    /// Absolute view pitch in radians, already clamped
+    pub pitch: f32,
+    /// Forward/backward axis in -1.0..=1.0
+    pub forward_move: f32,
+    /// Left/right axis in -1.0..=1.0
+    pub side_move: f32,
+    pub buttons: CmdButtons,
+}
+
+/// Sent when a controller touches the ground again after being airborne
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PlayerLanded {
+    pub entity: Entity,
+    /// Downward speed at the moment of impact in m/s
+    pub speed: f32,
+}
+
+/// The player's view camera, a child of the [`FpsController`] entity
+#[derive(Component)]
+pub struct PlayerCamera;
+
+/// Q/E lean state. The offset is already collision checked, so the view
+/// never ends up inside a wall.
+#[derive(Component, Default, Debug, Clone, Copy, Reflect)]
+pub struct Lean {
+    /// -1.0 (links) bis 1.0 (rechts)
+    pub amount: f32,
+    /// Seitlicher Versatz in Metern nach der Kollisionsprüfung
+    pub offset: f32,
+    /// Roll in Radiant, positiv = nach rechts geneigt
+    pub roll: f32,
+}
+
+impl Lean {
+    pub const MAX_OFFSET: f32 = 0.4;
+    pub const MAX_ROLL_DEG: f32 = 12.0;
+    /// Anteil von `amount` pro Sekunde
+    pub const SPEED: f32 = 5.0;
+    /// Radius der Kugel für die Kollisionsprüfung
+    pub const PROBE_RADIUS: f32 = 0.2;
+
+    /// Eye offset relative to the un-leaned view
+    pub fn view_offset(&self) -> Transform {
+        Transform::from_translation(Vec3::X * self.offset)
+            .with_rotation(Quat::from_rotation_z(-self.roll))
+    }
+}
+
+/// Head hitbox of the player, follows the lean
+#[derive(Component)]
+pub struct PlayerHead;
+
+/// Maximaler Pitch (nach oben/unten) in Radiant
+pub const MAX_PITCH: f32 = 1.5;
 
 #[derive(Resource)]
 pub struct CursorLocked(pub bool);
@@ -88,165 +200,300 @@ fn setup_fps_controller(
     
     commands.insert_resource(CursorLocked(true));
 
-    // Spawn FPS camera with physics and weapon inventory
-    let player_entity = commands.spawn((
-        Camera3d::default(),
+    // Spawn FPS player with physics and weapon inventory, the camera is a child
+    commands.spawn((
         Transform::from_xyz(0.0, 1.8, 5.0),
         FpsController::default(),
+        PlayerCmd::default(),
+        Lean::default(),
         RigidBody::Dynamic,
         Collider::capsule(0.4, 1.8), // Capsule collider for player
+        GameLayer::Player.layers(),
         Mass(70.0), // Player mass in kg
         LockedAxes::ROTATION_LOCKED, // Prevent physics rotation
         Friction::new(0.1),
         Restitution::new(0.0),
         crate::gameplay::weapons::PlayerInventory::default(),
-        crate::gameplay::interaction::PlayerHealth::default(),
+        crate::gameplay::weapons::Accuracy::default(),
+        crate::gameplay::weapons::ViewPunch::default(),
+        crate::gameplay::weapons::AimDownSights::default(),
+        crate::gameplay::damage::Health::default(),
+        crate::gameplay::damage::Armor::default(),
         crate::gameplay::audio::FootstepEmitter::default(),
-    )).id();
+    )).with_children(|player| {
+        player.spawn((
+            Camera3d::default(),
+            Transform::default(),
+            PlayerCamera,
+            crate::rendering::view_motion::ViewMotion::default(),
+            Name::new("PlayerCamera"),
+        ));
+        player.spawn((
+            Transform::default(),
+            Collider::sphere(Lean::PROBE_RADIUS),
+            GameLayer::Player.layers(),
+            ColliderDensity(0.0),
+            Sensor,
+            PlayerHead,
+            crate::gameplay::damage::HitGroup::Head,
+            Name::new("PlayerHead"),
+        ));
+    });
 }
 
-fn fps_controller_look(
+fn sample_player_cmd(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
     mut mouse_motion: EventReader<MouseMotion>,
-    mut query: Query<&mut Transform, With<FpsController>>,
-    controller_query: Query<&FpsController>,
     cursor_locked: Res<CursorLocked>,
+    mut query: Query<(&mut PlayerCmd, &FpsController)>,
 ) {
-    if !cursor_locked.0 {
-        return;
-    }
-
     let mut mouse_delta = Vec2::ZERO;
     for motion in mouse_motion.read() {
         mouse_delta += motion.delta;
     }
+    // Ohne Cursor-Lock keine Blickänderung
+    if !cursor_locked.0 {
+        mouse_delta = Vec2::ZERO;
+    }
+
+    let axis = |positive: KeyCode, negative: KeyCode| {
+        keyboard.pressed(positive) as i8 as f32 - keyboard.pressed(negative) as i8 as f32
+    };
+
+    for (mut cmd, controller) in query.iter_mut() {
+        if controller.enabled {
+            let sensitivity = controller.sensitivity * controller.sensitivity_scale;
+            cmd.yaw -= mouse_delta.x * sensitivity;
+            cmd.pitch = (cmd.pitch - mouse_delta.y * sensitivity)
+                .clamp(-MAX_PITCH, MAX_PITCH);
+        }
+
+        cmd.forward_move = axis(KeyCode::KeyW, KeyCode::KeyS);
+        cmd.side_move = axis(KeyCode::KeyD, KeyCode::KeyA);
+
+        let mut buttons = CmdButtons::NONE;
+        buttons.set(CmdButtons::JUMP, keyboard.pressed(KeyCode::Space));
+        buttons.set(CmdButtons::FAST_FALL, keyboard.pressed(KeyCode::ShiftLeft));
+        buttons.set(CmdButtons::ATTACK, mouse_buttons.pressed(MouseButton::Left));
+        buttons.set(CmdButtons::ATTACK2, mouse_buttons.pressed(MouseButton::Right));
+        buttons.set(CmdButtons::USE, keyboard.pressed(KeyCode::KeyF));
+        buttons.set(CmdButtons::LEAN_LEFT, keyboard.pressed(KeyCode::KeyQ));
+        buttons.set(CmdButtons::LEAN_RIGHT, keyboard.pressed(KeyCode::KeyE));
+        buttons.set(CmdButtons::DUCK, keyboard.pressed(KeyCode::ControlLeft));
+        buttons.set(CmdButtons::RELOAD, keyboard.pressed(KeyCode::KeyR));
+        cmd.buttons = buttons;
+    }
+}
+
+/// One command number per simulation step, not per rendered frame, so
+/// everything seeded by `PlayerCmd.tick` is independent of the frame rate
+fn advance_cmd_tick(mut query: Query<&mut PlayerCmd>) {
+    for mut cmd in query.iter_mut() {
+        cmd.tick = cmd.tick.wrapping_add(1);
+    }
+}
+
+fn fps_controller_look(
+    mut query: Query<(&mut Transform, &FpsController, &PlayerCmd)>,
+) {
+    for (mut transform, controller, cmd) in query.iter_mut() {
+        if !controller.enabled {
+            continue;
+        }
+
+        // Blickrichtung kommt ausschließlich aus dem Command
+        transform.rotation = Quat::from_euler(EulerRot::YXZ, cmd.yaw, cmd.pitch, 0.0);
+    }
+}
 
-    for mut transform in query.iter_mut() {
-        if let Ok(controller) = controller_query.single() {
-            if !controller.enabled {
-                continue;
+fn update_lean(
+    time: Res<Time>,
+    spatial_query: SpatialQuery,
+    mut query: Query<(Entity, &Transform, &PlayerCmd, &mut Lean, Has<Noclip>, Option<&Children>)>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, transform, cmd, mut lean, noclip, children) in query.iter_mut() {
+        let target = cmd.buttons.contains(CmdButtons::LEAN_RIGHT) as i8 as f32
+            - cmd.buttons.contains(CmdButtons::LEAN_LEFT) as i8 as f32;
+        let step = Lean::SPEED * dt;
+        lean.amount += (target - lean.amount).clamp(-step, step);
+
+        let desired = lean.amount * Lean::MAX_OFFSET;
+        let mut allowed = desired.abs();
+
+        // Seitlich testen, ob der Kopf Platz hat (im Noclip egal)
+        if !noclip && allowed > 0.0 {
+            let right = Quat::from_rotation_y(cmd.yaw) * Vec3::X;
+            if let Ok(direction) = Dir3::new(right * desired.signum()) {
+                // Eigener Körper und Kopf-Hitbox blockieren nicht
+                let own = std::iter::once(entity)
+                    .chain(children.into_iter().flat_map(|children| children.iter()));
+                if let Some(hit) = spatial_query.cast_shape(
+                    &Collider::sphere(Lean::PROBE_RADIUS),
+                    transform.translation,
+                    Quat::IDENTITY,
+                    direction,
+                    &ShapeCastConfig::from_max_distance(allowed),
+                    &movement_filter().with_excluded_entities(own),
+                ) {
+                    allowed = (hit.distance - 0.02).max(0.0);
+                }
             }
+        }
+
+        lean.offset = desired.signum() * allowed.min(desired.abs());
+        lean.roll = lean.offset / Lean::MAX_OFFSET * Lean::MAX_ROLL_DEG.to_radians();
+    }
+}
 
-            // Horizontal rotation (yaw)
-            transform.rotate_y(-mouse_delta.x * controller.sensitivity);
-            
-            // Vertical rotation (pitch)
-            let pitch_delta = -mouse_delta.y * controller.sensitivity;
-            let current_pitch = transform.rotation.to_euler(EulerRot::YXZ).1;
-            let new_pitch = (current_pitch + pitch_delta).clamp(-1.5, 1.5);
-            
-            // Reset rotation and apply yaw then pitch
-            let yaw = transform.rotation.to_euler(EulerRot::YXZ).0;
-            transform.rotation = Quat::from_euler(EulerRot::YXZ, yaw, new_pitch, 0.0);
+fn follow_lean_hitbox(
+    player_query: Query<&Lean>,
+    mut head_query: Query<(&mut Transform, &ChildOf), With<PlayerHead>>,
+) {
+    for (mut transform, child_of) in head_query.iter_mut() {
+        if let Ok(lean) = player_query.get(child_of.parent()) {
+            transform.translation = Vec3::X * lean.offset;
         }
     }
 }
 
 fn fps_controller_move(
     time: Res<Time>,
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&mut LinearVelocity, &Transform, &mut FpsController)>,
+    mut query: Query<(&mut LinearVelocity, &mut FpsController, &PlayerCmd, Has<Noclip>)>,
 ) {
     let dt = time.delta_secs();
 
-    for (mut lv, transform, mut ctrl) in &mut query {
+    for (mut lv, mut ctrl, cmd, noclip) in &mut query {
         if !ctrl.enabled { continue; }
 
-        // --- Jump-Input handling
-        // Taste losgelassen?
-        if !keyboard.pressed(KeyCode::Space) {
-            ctrl.jump_was_released = true;
-        }
-
-        // Buffer aktualisieren
-        if keyboard.just_pressed(KeyCode::Space) {
-            ctrl.jump_buffer_timer = ctrl.jump_buffer_time;
+        let mut velocity = lv.0;
+        if noclip {
+            simulate_noclip_move(&mut ctrl, &mut velocity, cmd);
         } else {
-            ctrl.jump_buffer_timer -= dt;
+            simulate_move(&mut ctrl, &mut velocity, cmd, dt);
         }
+        lv.0 = velocity;
+    }
+}
 
-        // --- Bewegungswunsch (wie gehabt, hier gekürzt)
-        let local_z = transform.local_z();
-        let forward = -Vec3::new(local_z.x, 0.0, local_z.z);
-        let right   =  Vec3::new(local_z.z, 0.0, -local_z.x);
+/// Free 6-DoF flight used while noclip is active: the view direction decides
+/// where "forward" goes, Jump/Duck move straight up and down.
+pub fn simulate_noclip_move(ctrl: &mut FpsController, velocity: &mut Vec3, cmd: &PlayerCmd) {
+    ctrl.prev_buttons = cmd.buttons;
 
-        let mut wish = Vec3::ZERO;
-        if keyboard.pressed(KeyCode::KeyW) { wish += forward; }
-        if keyboard.pressed(KeyCode::KeyS) { wish -= forward; }
-        if keyboard.pressed(KeyCode::KeyA) { wish -= right; }
-        if keyboard.pressed(KeyCode::KeyD) { wish += right; }
-        if wish.length_squared() > 0.0 { wish = wish.normalize() * ctrl.speed; }
+    let view = Quat::from_euler(EulerRot::YXZ, cmd.yaw, cmd.pitch, 0.0);
+    let forward = view * Vec3::NEG_Z;
+    let right = view * Vec3::X;
+    let up = cmd.buttons.contains(CmdButtons::JUMP) as i8 as f32
+        - cmd.buttons.contains(CmdButtons::DUCK) as i8 as f32;
 
-        let target_v = Vec2::new(wish.x, wish.z);
-        let curr_v   = Vec2::new(lv.x, lv.z);
-        
-        // Improved acceleration system
-        if ctrl.is_grounded {
-            // Ground movement with better deceleration
-            if target_v.length_squared() > 0.0 {
-                // Accelerating
-                let accel = ctrl.accel_ground;
-                let dv = (target_v - curr_v).clamp_length_max(accel * dt);
-                lv.x += dv.x;
-                lv.z += dv.y;
-            } else {
-                // Decelerating when no input
-                let decel = ctrl.accel_ground * 1.5; // Faster deceleration for more responsive feel
-                let dv = curr_v.clamp_length_max(decel * dt);
-                lv.x -= dv.x;
-                lv.z -= dv.y;
-            }
+    let wish = forward * cmd.forward_move + right * cmd.side_move + Vec3::Y * up;
+    // Kein Beschleunigen/Bremsen, Noclip soll direkt reagieren
+    *velocity = wish.normalize_or_zero() * ctrl.speed * 1.5;
+}
+
+/// Advances the movement state of one controller by a single tick.
+///
+/// Only `ctrl`, `velocity`, `cmd` and `dt` go in, so identical inputs always
+/// give identical results.
+pub fn simulate_move(ctrl: &mut FpsController, velocity: &mut Vec3, cmd: &PlayerCmd, dt: f32) {
+    let jump_held = cmd.buttons.contains(CmdButtons::JUMP);
+    let jump_pressed = jump_held && !ctrl.prev_buttons.contains(CmdButtons::JUMP);
+    ctrl.prev_buttons = cmd.buttons;
+
+    // --- Jump-Input handling
+    // Taste losgelassen?
+    if !jump_held {
+        ctrl.jump_was_released = true;
+    }
+
+    // Buffer aktualisieren
+    if jump_pressed {
+        ctrl.jump_buffer_timer = ctrl.jump_buffer_time;
+    } else {
+        ctrl.jump_buffer_timer -= dt;
+    }
+
+    // --- Bewegungswunsch aus Yaw und Achsen des Commands
+    let (sin_yaw, cos_yaw) = cmd.yaw.sin_cos();
+    let forward = Vec3::new(-sin_yaw, 0.0, -cos_yaw);
+    let right   = Vec3::new(cos_yaw, 0.0, -sin_yaw);
+
+    let mut wish = forward * cmd.forward_move + right * cmd.side_move;
+    if wish.length_squared() > 0.0 { wish = wish.normalize() * ctrl.speed; }
+
+    let target_v = Vec2::new(wish.x, wish.z);
+    let curr_v   = Vec2::new(velocity.x, velocity.z);
+    
+    // Improved acceleration system
+    if ctrl.is_grounded {
+        // Ground movement with better deceleration
+        if target_v.length_squared() > 0.0 {
+            // Accelerating
+            let accel = ctrl.accel_ground;
+            let dv = (target_v - curr_v).clamp_length_max(accel * dt);
+            velocity.x += dv.x;
+            velocity.z += dv.y;
         } else {
-            // Air movement with reduced control
-            let air_accel = ctrl.accel_air.max(ctrl.accel_ground * ctrl.air_control);
-            let dv = (target_v - curr_v).clamp_length_max(air_accel * dt);
-            lv.x += dv.x;
-            lv.z += dv.y;
+            // Decelerating when no input
+            let decel = ctrl.accel_ground * 1.5; // Faster deceleration for more responsive feel
+            let dv = curr_v.clamp_length_max(decel * dt);
+            velocity.x -= dv.x;
+            velocity.z -= dv.y;
         }
+    } else {
+        // Air movement with reduced control
+        let air_accel = ctrl.accel_air.max(ctrl.accel_ground * ctrl.air_control);
+        let dv = (target_v - curr_v).clamp_length_max(air_accel * dt);
+        velocity.x += dv.x;
+        velocity.z += dv.y;
+    }
 
-        // --- Sprungbedingungen
-        let buffer_ok = ctrl.jump_buffer_timer >= 0.0;
-        let ground_ok = ctrl.is_grounded || ctrl.coyote_timer > 0.0;
-        let air_ok    = !ground_ok && (ctrl.used_air_jumps < ctrl.max_air_jumps);
-        let input_ok  = ctrl.jump_was_released;      // Taste seit letztem Sprung losgelassen
-        let lock_ok   = !ctrl.jump_locked;           // nicht gesperrt (bis wieder Boden)
-
-        let can_jump = buffer_ok && (ground_ok || air_ok) && input_ok && lock_ok;
-
-        if can_jump {
-            // Improved jump calculation for more responsive jumping
-            let g = 12.0; // Increased gravity for faster falling (was 9.81)
-            let v0 = (2.0 * g * ctrl.jump_height).sqrt();
-
-            // Down-V eliminieren für konsistente Höhe
-            if lv.y < 0.0 { lv.y = 0.0; }
-            lv.y = v0;
-
-            // Zustände updaten
-            ctrl.jump_buffer_timer = -1.0;
-            ctrl.coyote_timer = 0.0;
-            ctrl.jump_was_released = false;
-            ctrl.jump_locked = true; // bleibt true, bis wieder stabil Boden
-            if !ground_ok { ctrl.used_air_jumps += 1; }
-        }
+    // --- Sprungbedingungen
+    let buffer_ok = ctrl.jump_buffer_timer >= 0.0;
+    let ground_ok = ctrl.is_grounded || ctrl.coyote_timer > 0.0;
+    let air_ok    = !ground_ok && (ctrl.used_air_jumps < ctrl.max_air_jumps);
+    let input_ok  = ctrl.jump_was_released;      // Taste seit letztem Sprung losgelassen
+    let lock_ok   = !ctrl.jump_locked;           // nicht gesperrt (bis wieder Boden)
+
+    let can_jump = buffer_ok && (ground_ok || air_ok) && input_ok && lock_ok;
+
+    if can_jump {
+        // Improved jump calculation for more responsive jumping
+        let g = 12.0; // Increased gravity for faster falling (was 9.81)
+        let v0 = (2.0 * g * ctrl.jump_height).sqrt();
+
+        // Down-V eliminieren für konsistente Höhe
+        if velocity.y < 0.0 { velocity.y = 0.0; }
+        velocity.y = v0;
+
+        // Zustände updaten
+        ctrl.jump_buffer_timer = -1.0;
+        ctrl.coyote_timer = 0.0;
+        ctrl.jump_was_released = false;
+        ctrl.jump_locked = true; // bleibt true, bis wieder stabil Boden
+        if !ground_ok { ctrl.used_air_jumps += 1; }
+    }
 
-        // Improved gravity and air movement
-        if !ctrl.is_grounded {
-            // Apply stronger gravity for faster falling
-            let gravity = 12.0; // Increased from 9.81
-            lv.y -= gravity * dt;
-            
-            // Optional: Faster fall when holding shift
-            if keyboard.pressed(KeyCode::ShiftLeft) {
-                lv.y -= gravity * 1.5 * dt; // Extra downward force
-            }
-            
-            // Terminal velocity cap
-            lv.y = lv.y.max(-25.0); // Faster terminal velocity (was no cap)
-        } else {
-            // Light ground adhesion (only when really grounded)
-            if lv.y < 0.0 {
-                lv.y = lv.y.max(-2.0);
-            }
+    // Improved gravity and air movement
+    if !ctrl.is_grounded {
+        // Apply stronger gravity for faster falling
+        let gravity = 12.0; // Increased from 9.81
+        velocity.y -= gravity * dt;
+        
+        // Optional: Faster fall when holding shift
+        if cmd.buttons.contains(CmdButtons::FAST_FALL) {
+            velocity.y -= gravity * 1.5 * dt; // Extra downward force
+        }
+        
+        // Terminal velocity cap
+        velocity.y = velocity.y.max(-25.0); // Faster terminal velocity (was no cap)
+    } else {
+        // Light ground adhesion (only when really grounded)
+        if velocity.y < 0.0 {
+            velocity.y = velocity.y.max(-2.0);
         }
     }
 }
@@ -275,12 +522,22 @@ fn toggle_cursor_lock(
 
 fn check_grounded(
     time: Res<Time>,
-    mut controllers: Query<(&Transform, &Collider, &mut FpsController, Option<&LinearVelocity>)>,
+    mut controllers: Query<(Entity, &Transform, &Collider, &mut FpsController, Option<&LinearVelocity>, Has<Noclip>)>,
     spatial_query: SpatialQuery,
+    mut landed_events: EventWriter<PlayerLanded>,
+    mut debug_rays: ResMut<crate::core::physics::DebugRays>,
 ) {
-    for (transform, collider, mut ctrl, lv_opt) in controllers.iter_mut() {
+    for (entity, transform, collider, mut ctrl, lv_opt, noclip) in controllers.iter_mut() {
         ctrl.coyote_timer -= time.delta_secs();
 
+        // Im Noclip gibt es keinen Boden (und keinen Fallschaden)
+        if noclip {
+            ctrl.ground_frames = 0;
+            ctrl.is_grounded = false;
+            ctrl.fall_speed = 0.0;
+            continue;
+        }
+
         // Collider-Maße (optional: aus shape() auslesen; ansonsten fest)
         let mut radius = 0.4;
         let mut half_height = 0.9;
@@ -293,13 +550,21 @@ fn check_grounded(
         let skin = ctrl.skin_width.max(0.02);
         let start = Vec3::new(transform.translation.x, foot_y + skin, transform.translation.z);
 
-        let hit_ok = if let Some(hit) = spatial_query.cast_ray(
+        let hit = spatial_query.cast_ray(
             start,
             Dir3::NEG_Y,
             skin * 2.0,
             false,
-            &SpatialQueryFilter::default(),
-        ) {
+            &movement_filter().with_excluded_entities([entity]),
+        );
+
+        let now = time.elapsed_secs();
+        match hit {
+            Some(hit) => debug_rays.add(now, start, start - Vec3::Y * hit.distance, Some(hit.normal), Color::srgb(0.2, 1.0, 0.2)),
+            None => debug_rays.add(now, start, start - Vec3::Y * skin * 2.0, None, Color::srgb(0.2, 0.4, 0.2)),
+        }
+
+        let hit_ok = if let Some(hit) = hit {
             let max_ny = (ctrl.max_slope_deg.to_radians()).cos();
             hit.normal.y >= max_ny
         } else {
@@ -322,10 +587,20 @@ fn check_grounded(
         let was_grounded = ctrl.is_grounded;
         ctrl.is_grounded = ctrl.ground_frames >= 2;
 
+        // Fallgeschwindigkeit merken, beim Aufsetzen ist die Velocity schon gebremst
+        if !ctrl.is_grounded {
+            if let Some(lv) = lv_opt {
+                ctrl.fall_speed = ctrl.fall_speed.max(-lv.y);
+            }
+        }
+
         // Reset für Luft-Sprünge und Jump-Lock, wenn wir „wirklich“ wieder am Boden sind
         if ctrl.is_grounded && !was_grounded {
             ctrl.used_air_jumps = 0;
             ctrl.jump_locked = false; // erst am Boden wird wieder entsperrt
+
+            landed_events.write(PlayerLanded { entity, speed: ctrl.fall_speed });
+            ctrl.fall_speed = 0.0;
         }
         
         // Ensure jump lock is maintained until we're stable on ground
@@ -337,3 +612,141 @@ fn check_grounded(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+
+    fn run_commands(cmds: &[PlayerCmd]) -> (Vec3, FpsController) {
+        let mut ctrl = FpsController::default();
+        ctrl.is_grounded = true;
+        let mut velocity = Vec3::ZERO;
+        for cmd in cmds {
+            simulate_move(&mut ctrl, &mut velocity, cmd, 1.0 / 64.0);
+        }
+        (velocity, ctrl)
+    }
+
+    /// Headless app with physics and only the simulation systems: no input
+    /// sampling and no keyboard or mouse resources, the commands are the only input
+    fn simulation_app() -> App {
+        let mut app = App::new();
+        app
+            .add_plugins((
+                MinimalPlugins,
+                TransformPlugin,
+                bevy::asset::AssetPlugin::default(),
+                PhysicsPlugins::default(),
+            ))
+            .init_asset::<Mesh>()
+            .init_resource::<crate::core::physics::DebugRays>()
+            .add_event::<PlayerLanded>()
+            .insert_resource(Time::<Fixed>::from_hz(64.0))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_micros(15_625)))
+            .add_systems(FixedUpdate, (
+                advance_cmd_tick,
+                fps_controller_look,
+                update_lean,
+                fps_controller_move,
+                check_grounded,
+            ).chain());
+
+        // Boden
+        app.world_mut().spawn((
+            Transform::from_xyz(0.0, -0.5, 0.0),
+            RigidBody::Static,
+            Collider::cuboid(100.0, 1.0, 100.0),
+            GameLayer::World.layers(),
+        ));
+        app
+    }
+
+    /// Player body standing on the ground, with the head hitbox like in `setup_fps_controller`
+    fn spawn_test_player(app: &mut App, cmd: PlayerCmd) -> Entity {
+        app.world_mut()
+            .spawn((
+                Transform::from_xyz(0.0, 1.3, 0.0),
+                FpsController::default(),
+                cmd,
+                Lean::default(),
+                RigidBody::Dynamic,
+                Collider::capsule(0.4, 1.8),
+                GameLayer::Player.layers(),
+                LockedAxes::ROTATION_LOCKED,
+            ))
+            .with_children(|player| {
+                player.spawn((
+                    Transform::default(),
+                    Collider::sphere(Lean::PROBE_RADIUS),
+                    GameLayer::Player.layers(),
+                    ColliderDensity(0.0),
+                    Sensor,
+                    PlayerHead,
+                ));
+            })
+            .id()
+    }
+
+    #[test]
+    fn movement_only_depends_on_commands() {
+        let run = || {
+            let mut app = simulation_app();
+            let player = spawn_test_player(&mut app, PlayerCmd {
+                forward_move: 1.0,
+                ..default()
+            });
+            for _ in 0..64 {
+                app.update();
+            }
+            let world = app.world();
+            (*world.get::<Transform>(player).unwrap(), world.get::<PlayerCmd>(player).unwrap().tick)
+        };
+
+        let (transform_a, tick_a) = run();
+        let (transform_b, tick_b) = run();
+
+        // Yaw 0 schaut entlang -Z
+        assert!(transform_a.translation.z < -1.0, "player did not move: {:?}", transform_a.translation);
+        assert!(transform_a.translation.x.abs() < 1e-3);
+        assert_eq!(
+            transform_a.translation.to_array().map(f32::to_bits),
+            transform_b.translation.to_array().map(f32::to_bits),
+        );
+        // Ein Tick pro Simulationsschritt, nicht pro Frame
+        assert_eq!(tick_a, tick_b);
+        assert!(tick_a > 0 && tick_a <= 64);
+    }
+
+    #[test]
+    fn lean_reaches_full_offset_in_open_space() {
+        let mut app = simulation_app();
+        let player = spawn_test_player(&mut app, PlayerCmd {
+            buttons: CmdButtons::LEAN_RIGHT,
+            ..default()
+        });
+        for _ in 0..64 {
+            app.update();
+        }
+
+        let lean = app.world().get::<Lean>(player).unwrap();
+        assert!((lean.offset - Lean::MAX_OFFSET).abs() < 1e-4, "offset {}", lean.offset);
+    }
+
+    #[test]
+    fn movement_follows_command_yaw() {
+        let cmd = PlayerCmd {
+            yaw: std::f32::consts::FRAC_PI_2,
+            forward_move: 1.0,
+            ..default()
+        };
+        let (velocity, _) = run_commands(&[cmd; 32]);
+
+        // Yaw von +90° schaut entlang -X
+        assert!(velocity.x < -1.0);
+        assert!(velocity.z.abs() < 1e-4);
+    }
+}
@@ -1,17 +1,105 @@
 use bevy::prelude::*;
 use avian3d::prelude::*;
 
+use crate::debug::inspector::DebugSettings;
+use crate::gameplay::props::{spawn_prop, PropDrop, PropMaterial, PropSettings};
+use crate::gameplay::surfaces::SurfaceTexture;
+
 pub struct PhysicsPlugin;
 
 impl Plugin for PhysicsPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_plugins(PhysicsPlugins::default())
+            .add_plugins((
+                PhysicsPlugins::default(),
+                PhysicsDebugPlugin::default(),
+            ))
+            // Debug-Rendering startet aus und wird über DebugSettings geschaltet
+            .insert_gizmo_config(
+                PhysicsGizmos::none(),
+                GizmoConfig {
+                    enabled: false,
+                    ..default()
+                },
+            )
+            .init_resource::<DebugRays>()
             .add_systems(Startup, setup_physics_world)
-            .add_systems(Update, handle_physics_debug);
+            .add_systems(Update, (
+                handle_physics_debug,
+                draw_debug_rays,
+            ).chain());
     }
 }
 
+/// Collision layers for the game's object kinds
+#[derive(PhysicsLayer, Clone, Copy, Debug, Default)]
+pub enum GameLayer {
+    #[default]
+    Default,
+    /// Static map geometry
+    World,
+    /// Player bodies and hitboxes
+    Player,
+    /// Dynamic physics objects
+    Prop,
+    /// Weapon, health and ammo pickups
+    Pickup,
+    /// Trigger volumes
+    Trigger,
+    Projectile,
+    /// Gibs and other short-lived debris, only collides with the world
+    Debris,
+}
+
+impl GameLayer {
+    /// Membership in this layer plus the layers it should collide with
+    pub fn layers(self) -> CollisionLayers {
+        match self {
+            GameLayer::Default | GameLayer::World => CollisionLayers::new(self, LayerMask::ALL),
+            GameLayer::Player => CollisionLayers::new(self, [
+                GameLayer::World,
+                GameLayer::Player,
+                GameLayer::Prop,
+                GameLayer::Pickup,
+                GameLayer::Trigger,
+                GameLayer::Projectile,
+            ]),
+            GameLayer::Prop => CollisionLayers::new(self, [
+                GameLayer::World,
+                GameLayer::Player,
+                GameLayer::Prop,
+                GameLayer::Trigger,
+                GameLayer::Projectile,
+            ]),
+            GameLayer::Pickup => CollisionLayers::new(self, [GameLayer::Player]),
+            GameLayer::Trigger => CollisionLayers::new(self, [GameLayer::Player, GameLayer::Prop]),
+            GameLayer::Projectile => CollisionLayers::new(self, [
+                GameLayer::World,
+                GameLayer::Player,
+                GameLayer::Prop,
+            ]),
+            GameLayer::Debris => CollisionLayers::new(self, [GameLayer::World]),
+        }
+    }
+}
+
+/// Filter for hitscan weapons: everything that can stop a bullet, never pickups or triggers.
+/// Unlike [`movement_filter`] it includes debris, so gibs and ragdolls can be shot around.
+pub fn hitscan_filter() -> SpatialQueryFilter {
+    SpatialQueryFilter::from_mask([GameLayer::World, GameLayer::Player, GameLayer::Prop, GameLayer::Debris])
+}
+
+/// Filter for ground checks and movement probes: things you can stand on or bump into.
+/// Debris is left out since players walk through gibs and ragdoll bones.
+pub fn movement_filter() -> SpatialQueryFilter {
+    SpatialQueryFilter::from_mask([GameLayer::World, GameLayer::Player, GameLayer::Prop])
+}
+
+/// Filter for queries that only care about static map geometry
+pub fn world_filter() -> SpatialQueryFilter {
+    SpatialQueryFilter::from_mask(GameLayer::World)
+}
+
 #[derive(Component)]
 pub struct PhysicsObject;
 
@@ -28,20 +116,34 @@ fn setup_physics_world(
 ) {
     // Add some physics objects for testing
     
-    // Create a few dynamic cubes
-    for i in 0..3 {
-        commands.spawn((
-            Mesh3d(meshes.add(Cuboid::new(1.0, 1.0, 1.0))),
-            MeshMaterial3d(materials.add(StandardMaterial {
-                base_color: Color::srgb(0.8, 0.2, 0.2),
-                ..default()
-            })),
-            Transform::from_xyz(0.0, 5.0 + i as f32 * 2.0, 0.0),
-            RigidBody::Dynamic,
-            Collider::cuboid(1.0, 1.0, 1.0),
-            PhysicsObject,
-            DynamicCollider,
-        ));
+    // Pushable crate, breakable crate with a health pack and an explosive barrel
+    let test_props = [
+        PropSettings {
+            material: PropMaterial::Wood,
+            ..default()
+        },
+        PropSettings {
+            material: PropMaterial::Wood,
+            health: Some(30.0),
+            drop: Some(PropDrop::HealthPack(25.0)),
+            ..default()
+        },
+        PropSettings {
+            size: Vec3::new(0.6, 0.9, 0.6),
+            material: PropMaterial::Metal,
+            health: Some(20.0),
+            explode_magnitude: 100.0,
+            ..default()
+        },
+    ];
+    for (i, settings) in test_props.iter().enumerate() {
+        spawn_prop(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            Transform::from_xyz(i as f32 * 2.0 - 2.0, 1.0, 0.0),
+            settings,
+        );
     }
 
     // Add ground collider
@@ -49,7 +151,9 @@ fn setup_physics_world(
         Transform::from_xyz(0.0, -0.25, 0.0),
         RigidBody::Static,
         Collider::cuboid(50.0, 0.25, 50.0),
+        GameLayer::World.layers(),
         StaticCollider,
+        SurfaceTexture("OUT_GRASS1".to_string()),
     ));
 
     // Add wall colliders for the test cubes
@@ -58,18 +162,97 @@ fn setup_physics_world(
             Transform::from_xyz(i as f32 * 4.0 - 8.0, 1.0, -10.0),
             RigidBody::Static,
             Collider::cuboid(1.0, 1.0, 1.0),
+            GameLayer::World.layers(),
             StaticCollider,
+            SurfaceTexture("METALWALL1".to_string()),
         ));
     }
 }
 
+/// Rays from spatial queries (hitscan, ground checks) kept around for a while
+/// so they can be drawn as gizmos. avian3d only draws its own `RayCaster`s.
+#[derive(Resource, Default)]
+pub struct DebugRays {
+    pub enabled: bool,
+    pub duration: f32,
+    rays: Vec<DebugRay>,
+}
+
+struct DebugRay {
+    start: Vec3,
+    end: Vec3,
+    hit_normal: Option<Vec3>,
+    color: Color,
+    expires_at: f32,
+}
+
+impl DebugRays {
+    /// Records a ray from `start` to `end`; `hit_normal` is set if the ray hit something at `end`
+    pub fn add(&mut self, now: f32, start: Vec3, end: Vec3, hit_normal: Option<Vec3>, color: Color) {
+        if !self.enabled {
+            return;
+        }
+
+        self.rays.push(DebugRay {
+            start,
+            end,
+            hit_normal,
+            color,
+            expires_at: now + self.duration,
+        });
+    }
+}
+
 fn handle_physics_debug(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    // Note: PhysicsDebugConfig might not be available in avian3d 0.3
-    // TODO: Check avian3d documentation for proper debug rendering setup
+    debug_settings: Res<DebugSettings>,
+    mut config_store: ResMut<GizmoConfigStore>,
+    mut debug_rays: ResMut<DebugRays>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::F3) {
-        info!("Physics debug toggle pressed (debug rendering not yet implemented)");
+    if !debug_settings.is_changed() {
+        return;
+    }
+
+    // F2: Collider-Wireframes, F3: Kontakte, AABBs und Raycasts
+    let mut gizmos = PhysicsGizmos::none();
+    if debug_settings.show_physics_wireframes {
+        gizmos = gizmos.with_collider_color(Color::srgb(1.0, 0.6, 0.0));
+    }
+    if debug_settings.show_physics_details {
+        gizmos = gizmos
+            .with_aabb_color(Color::srgb(0.3, 0.3, 0.9))
+            .with_contact_point_color(Color::srgb(0.0, 1.0, 1.0))
+            .with_contact_normal_color(Color::srgb(1.0, 0.0, 1.0))
+            .with_raycast_color(Color::srgb(1.0, 0.0, 0.0))
+            .with_raycast_point_color(Color::srgb(1.0, 1.0, 0.0))
+            .with_raycast_normal_color(Color::srgb(1.0, 0.0, 1.0))
+            .with_shapecast_color(Color::srgb(1.0, 0.0, 0.0));
+    }
+
+    let (config, physics_gizmos) = config_store.config_mut::<PhysicsGizmos>();
+    config.enabled = debug_settings.show_physics_wireframes || debug_settings.show_physics_details;
+    *physics_gizmos = gizmos;
+
+    debug_rays.enabled = debug_settings.show_physics_details;
+    debug_rays.duration = debug_settings.debug_ray_duration;
+    if !debug_rays.enabled {
+        debug_rays.rays.clear();
+    }
+}
+
+fn draw_debug_rays(
+    time: Res<Time>,
+    mut debug_rays: ResMut<DebugRays>,
+    mut gizmos: Gizmos,
+) {
+    let now = time.elapsed_secs();
+    debug_rays.rays.retain(|ray| ray.expires_at > now);
+
+    for ray in debug_rays.rays.iter() {
+        gizmos.line(ray.start, ray.end, ray.color);
+        if let Some(normal) = ray.hit_normal {
+            gizmos.sphere(Isometry3d::from_translation(ray.end), 0.03, ray.color);
+            gizmos.arrow(ray.end, ray.end + normal * 0.25, ray.color);
+        }
     }
 }
 
@@ -82,6 +265,7 @@ pub fn add_static_collider(
     commands.entity(entity).insert((
         RigidBody::Static,
         collider,
+        GameLayer::World.layers(),
         StaticCollider,
     ));
 }
@@ -95,6 +279,7 @@ pub fn add_dynamic_collider(
     commands.entity(entity).insert((
         RigidBody::Dynamic,
         collider,
+        GameLayer::Prop.layers(),
         Mass(mass),
         DynamicCollider,
         PhysicsObject,
@@ -0,0 +1,201 @@
+//! Developer cheats
+//!
+//! Noclip, god mode, notarget and "give all weapons" for faster level testing.
+//! Cheats only work while `CheatSettings::enabled` is set; starting or joining
+//! a multiplayer session switches them off and strips them from all players.
+
+use bevy::prelude::*;
+use avian3d::prelude::*;
+
+use crate::core::fps_controller::FpsController;
+use crate::gameplay::damage::Health;
+use crate::content::weapon_definitions::{WeaponDefinition, WeaponDefinitions};
+use crate::gameplay::weapons::{self, AmmoType, PlayerInventory, Weapon};
+use crate::ui::networking::multiplayer::NetworkSettings;
+
+pub struct CheatsPlugin;
+
+impl Plugin for CheatsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<CheatSettings>()
+            .add_systems(Startup, setup_cheats)
+            .add_systems(Update, (
+                enforce_cheat_policy,
+                handle_cheat_input,
+            ).chain())
+            // Nach allen Gameplay-Systemen, damit kein Schaden "durchrutscht"
+            .add_systems(PostUpdate, apply_god_mode);
+    }
+}
+
+/// Global cheat switch, comparable to `sv_cheats`
+#[derive(Resource)]
+pub struct CheatSettings {
+    pub enabled: bool,
+}
+
+impl Default for CheatSettings {
+    fn default() -> Self {
+        Self {
+            // Nur in Debug-Builds standardmäßig erlaubt
+            enabled: cfg!(debug_assertions),
+        }
+    }
+}
+
+/// Disables the collider and lets the controller fly freely
+#[derive(Component, Default)]
+pub struct Noclip;
+
+/// `Health` of this entity can't drop
+#[derive(Component, Default)]
+pub struct GodMode;
+
+/// AI target selection should ignore this entity
+#[derive(Component, Default)]
+pub struct NoTarget;
+
+fn setup_cheats(cheat_settings: Res<CheatSettings>) {
+    info!("🎮 Cheats: {}", if cheat_settings.enabled { "available" } else { "disabled" });
+    info!("   Alt+N - Toggle noclip");
+    info!("   Alt+G - Toggle god mode");
+    info!("   Alt+T - Toggle notarget");
+    info!("   Alt+I - Give all weapons");
+}
+
+fn enforce_cheat_policy(
+    mut commands: Commands,
+    network_settings: Option<Res<NetworkSettings>>,
+    mut cheat_settings: ResMut<CheatSettings>,
+    cheaters: Query<(Entity, Has<Noclip>), Or<(With<Noclip>, With<GodMode>, With<NoTarget>)>>,
+) {
+    let in_session = network_settings.is_some_and(|network| network.is_server || network.is_client);
+    if in_session && cheat_settings.enabled {
+        cheat_settings.enabled = false;
+        warn!("Cheats disabled for multiplayer session");
+    }
+
+    if cheat_settings.enabled {
+        return;
+    }
+
+    for (entity, noclip) in cheaters.iter() {
+        if noclip {
+            set_noclip(&mut commands, entity, false);
+        }
+        commands.entity(entity).remove::<(GodMode, NoTarget)>();
+    }
+}
+
+fn handle_cheat_input(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    cheat_settings: Res<CheatSettings>,
+    mut player_query: Query<(Entity, &mut PlayerInventory, Has<Noclip>, Has<GodMode>, Has<NoTarget>), With<FpsController>>,
+    mut weapon_query: Query<&mut Weapon>,
+    definitions: Res<WeaponDefinitions>,
+    weapon_assets: Res<Assets<WeaponDefinition>>,
+) {
+    if !keyboard_input.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]) {
+        return;
+    }
+
+    let cheat_keys = [KeyCode::KeyN, KeyCode::KeyG, KeyCode::KeyT, KeyCode::KeyI];
+    if !keyboard_input.any_just_pressed(cheat_keys) {
+        return;
+    }
+
+    if !cheat_settings.enabled {
+        info!("Cheats are disabled");
+        return;
+    }
+
+    for (entity, mut inventory, noclip, god_mode, no_target) in player_query.iter_mut() {
+        if keyboard_input.just_pressed(KeyCode::KeyN) {
+            set_noclip(&mut commands, entity, !noclip);
+            info!("noclip {}", if noclip { "OFF" } else { "ON" });
+        }
+
+        if keyboard_input.just_pressed(KeyCode::KeyG) {
+            toggle_marker::<GodMode>(&mut commands, entity, god_mode);
+            info!("godmode {}", if god_mode { "OFF" } else { "ON" });
+        }
+
+        if keyboard_input.just_pressed(KeyCode::KeyT) {
+            toggle_marker::<NoTarget>(&mut commands, entity, no_target);
+            info!("notarget {}", if no_target { "OFF" } else { "ON" });
+        }
+
+        if keyboard_input.just_pressed(KeyCode::KeyI) {
+            give_all_weapons(&mut commands, &mut inventory, &mut weapon_query, &definitions, &weapon_assets);
+        }
+    }
+}
+
+fn apply_god_mode(mut query: Query<&mut Health, With<GodMode>>) {
+    for mut health in query.iter_mut() {
+        if health.current < health.maximum {
+            health.current = health.maximum;
+        }
+    }
+}
+
+fn toggle_marker<T: Component + Default>(commands: &mut Commands, entity: Entity, active: bool) {
+    if active {
+        commands.entity(entity).remove::<T>();
+    } else {
+        commands.entity(entity).insert(T::default());
+    }
+}
+
+/// Switches noclip on or off: no collider, no gravity, free flight
+pub fn set_noclip(commands: &mut Commands, entity: Entity, enabled: bool) {
+    if enabled {
+        commands.entity(entity).insert((Noclip, ColliderDisabled, GravityScale(0.0)));
+    } else {
+        commands.entity(entity)
+            .remove::<(Noclip, ColliderDisabled)>()
+            .insert(GravityScale(1.0));
+    }
+}
+
+/// Fills the inventory with every defined weapon and refills all ammo
+fn give_all_weapons(
+    commands: &mut Commands,
+    inventory: &mut PlayerInventory,
+    weapon_query: &mut Query<&mut Weapon>,
+    definitions: &WeaponDefinitions,
+    weapon_assets: &Assets<WeaponDefinition>,
+) {
+    for id in definitions.ids() {
+        let Some(weapon) = weapons::create_weapon(definitions, weapon_assets, id, u32::MAX) else {
+            continue;
+        };
+
+        let mut owned = false;
+        for &weapon_entity in inventory.weapons.iter() {
+            if let Ok(mut owned_weapon) = weapon_query.get_mut(weapon_entity) {
+                if owned_weapon.id == weapon.id {
+                    owned_weapon.magazine = weapon.magazine;
+                    owned = true;
+                }
+            }
+        }
+        if owned {
+            continue;
+        }
+
+        let weapon_entity = commands.spawn(weapon).id();
+        inventory.weapons.push(weapon_entity);
+        if inventory.held_weapon.is_none() {
+            inventory.held_weapon = Some(weapon_entity);
+        }
+    }
+
+    for ammo_type in AmmoType::ALL {
+        inventory.give_ammo(ammo_type, ammo_type.max_carry());
+    }
+
+    info!("Gave all weapons");
+}
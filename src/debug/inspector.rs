@@ -25,6 +25,10 @@ impl Plugin for DebugPlugin {
 pub struct DebugSettings {
     pub show_inspector: bool,
     pub show_physics_wireframes: bool,
+    /// Contacts, AABBs and raycasts (hitscan, ground checks)
+    pub show_physics_details: bool,
+    /// How long hitscan and ground-check rays stay visible, in seconds
+    pub debug_ray_duration: f32,
     pub show_fps_overlay: bool,
     pub log_entity_counts: bool,
     pub log_performance: bool,
@@ -35,6 +39,8 @@ impl Default for DebugSettings {
         Self {
             show_inspector: true,
             show_physics_wireframes: false,
+            show_physics_details: false,
+            debug_ray_duration: 2.0,
             show_fps_overlay: true,
             log_entity_counts: false,
             log_performance: false,
@@ -47,9 +53,10 @@ fn setup_debug_tools(_commands: Commands) {
     info!("📋 Debug Controls:");
     info!("   F1 - Toggle inspector");
     info!("   F2 - Toggle physics wireframes");
-    info!("   F3 - Toggle FPS overlay");
+    info!("   F3 - Toggle physics contacts, AABBs and rays");
     info!("   F4 - Toggle entity count logging");
     info!("   F5 - Toggle performance logging");
+    info!("   F6 - Toggle FPS overlay");
     info!("   ESC - Exit application");
 }
 
@@ -68,6 +75,11 @@ fn handle_debug_input(
     }
     
     if keyboard_input.just_pressed(KeyCode::F3) {
+        debug_settings.show_physics_details = !debug_settings.show_physics_details;
+        info!("⚡ Physics contacts/AABBs/rays: {}", if debug_settings.show_physics_details { "ON" } else { "OFF" });
+    }
+    
+    if keyboard_input.just_pressed(KeyCode::F6) {
         debug_settings.show_fps_overlay = !debug_settings.show_fps_overlay;
         info!("📊 FPS overlay: {}", if debug_settings.show_fps_overlay { "ON" } else { "OFF" });
     }
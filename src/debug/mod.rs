@@ -4,7 +4,10 @@
 //! - Inspector integration
 //! - Performance monitoring
 //! - Development controls
+//! - Developer cheats (noclip, god mode, ...)
 
 pub mod inspector;
+pub mod cheats;
 
-pub use inspector::DebugPlugin;
\ No newline at end of file
+pub use inspector::DebugPlugin;
+pub use cheats::CheatsPlugin;
\ No newline at end of file
@@ -1,6 +1,9 @@
+use bevy::audio::Volume;
 use bevy::prelude::*;
 use avian3d::prelude::*;
 
+use crate::gameplay::surfaces::{SurfaceImpact, SurfaceMaterial, SurfaceProperties};
+
 pub struct AudioPlugin;
 
 impl Plugin for AudioPlugin {
@@ -9,6 +12,8 @@ impl Plugin for AudioPlugin {
             .add_systems(Startup, setup_audio_system)
             .add_systems(Update, (
                 footstep_audio_system,
+                landing_audio_system,
+                impact_audio_system,
             ));
     }
 }
@@ -18,6 +23,7 @@ pub struct FootstepEmitter {
     pub last_step_time: f32,
     pub step_interval: f32,
     pub volume: f32,
+    pub step_count: u32,
 }
 
 impl Default for FootstepEmitter {
@@ -26,6 +32,7 @@ impl Default for FootstepEmitter {
             last_step_time: 0.0,
             step_interval: 0.5, // Steps every 0.5 seconds when moving
             volume: 0.3,
+            step_count: 0,
         }
     }
 }
@@ -35,7 +42,7 @@ pub struct ImpactSound {
     pub sound_type: ImpactType,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
 pub enum ImpactType {
     Metal,
     Wood,
@@ -44,15 +51,31 @@ pub enum ImpactType {
     Soft,
 }
 
+impl ImpactType {
+    /// Bullet impact sound, `variant` picks one of the five takes
+    pub fn impact_sound(self, variant: u32) -> String {
+        let name = match self {
+            Self::Metal => "impactMetal_light",
+            Self::Wood => "impactWood_light",
+            Self::Glass => "impactGlass_light",
+            Self::Concrete => "impactGeneric_light",
+            Self::Soft => "impactSoft_medium",
+        };
+        format!("sounds/impact/{}_{:03}.ogg", name, variant % 5)
+    }
+}
+
 fn setup_audio_system() {
-    info!("Audio system initialized (placeholder - audio disabled for build compatibility)");
+    info!("Audio system initialized (impact and landing sounds, footsteps still placeholder)");
 }
 
 fn footstep_audio_system(
     time: Res<Time>,
-    mut footstep_query: Query<(&mut FootstepEmitter, &LinearVelocity)>,
+    spatial_query: SpatialQuery,
+    surface_query: Query<&SurfaceProperties>,
+    mut footstep_query: Query<(Entity, &Transform, &mut FootstepEmitter, &LinearVelocity, Option<&Children>)>,
 ) {
-    for (mut emitter, velocity) in footstep_query.iter_mut() {
+    for (entity, transform, mut emitter, velocity, children) in footstep_query.iter_mut() {
         let speed = velocity.length();
         
         // Only play footsteps when moving at a reasonable speed
@@ -64,15 +87,103 @@ fn footstep_audio_system(
             
             if current_time - emitter.last_step_time > adjusted_interval {
                 emitter.last_step_time = current_time;
+                emitter.step_count = emitter.step_count.wrapping_add(1);
+
+                let material = ground_material(&spatial_query, &surface_query, entity, children, transform.translation);
                 
                 // TODO: Play actual footstep sounds when audio is enabled
                 // For now, just log the event
-                debug!("Footstep at time: {}", current_time);
+                debug!("Footstep on {:?} at time: {} ({})", material, current_time, material.footstep_sound(emitter.step_count));
             }
         }
     }
 }
 
+/// Surface under a body (like PM_CatagorizeTextureType), ignoring the body
+/// and its own hitboxes
+fn ground_material(
+    spatial_query: &SpatialQuery,
+    surface_query: &Query<&SurfaceProperties>,
+    entity: Entity,
+    children: Option<&Children>,
+    position: Vec3,
+) -> SurfaceMaterial {
+    let own = std::iter::once(entity)
+        .chain(children.into_iter().flat_map(|children| children.iter()));
+    spatial_query
+        .cast_ray(
+            position,
+            Dir3::NEG_Y,
+            2.0,
+            true,
+            &crate::core::physics::movement_filter().with_excluded_entities(own),
+        )
+        .and_then(|hit| surface_query.get(hit.entity).ok())
+        .map(|surface| surface.material)
+        .unwrap_or_default()
+}
+
+fn landing_audio_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    spatial_query: SpatialQuery,
+    surface_query: Query<&SurfaceProperties>,
+    mut landed_events: EventReader<crate::core::fps_controller::PlayerLanded>,
+    fall_damage: Res<crate::gameplay::interaction::FallDamageSettings>,
+    mut footstep_query: Query<(&Transform, &mut FootstepEmitter, Option<&Children>)>,
+    time: Res<Time>,
+) {
+    for landed in landed_events.read() {
+        let Ok((transform, mut emitter, children)) = footstep_query.get_mut(landed.entity) else {
+            continue;
+        };
+
+        // Landung zählt als Schritt, damit nicht direkt danach noch einer kommt
+        emitter.last_step_time = time.elapsed_secs();
+        emitter.step_count = emitter.step_count.wrapping_add(1);
+
+        // Harte Landung klingt dumpf, sonst ein lauterer Schritt auf dem Untergrund
+        let (sound, volume) = if fall_damage.damage_for_speed(landed.speed) > 0.0 {
+            (format!("sounds/impact/impactPunch_heavy_{:03}.ogg", emitter.step_count % 5), 0.8)
+        } else {
+            let material = ground_material(&spatial_query, &surface_query, landed.entity, children, transform.translation);
+            (material.footstep_sound(emitter.step_count), (emitter.volume * 1.5).min(1.0))
+        };
+        debug!("Landing at {:.1} m/s ({})", landed.speed, sound);
+
+        commands.spawn((
+            AudioPlayer::new(asset_server.load(sound)),
+            PlaybackSettings::DESPAWN.with_volume(Volume::Linear(volume)),
+        ));
+    }
+}
+
+fn impact_audio_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut impact_events: EventReader<SurfaceImpact>,
+    mut variant: Local<u32>,
+) {
+    // Schrotladungen: pro Frame nur ein Sound je Oberflächentyp
+    let mut played: Vec<ImpactType> = Vec::new();
+
+    for impact in impact_events.read() {
+        let impact_type = impact.surface.impact_type;
+        if played.contains(&impact_type) {
+            continue;
+        }
+        played.push(impact_type);
+
+        *variant = variant.wrapping_add(1);
+        let sound = impact_type.impact_sound(*variant);
+        debug!("{:?} impact sound at {:?} ({})", impact_type, impact.point, sound);
+        commands.spawn((
+            AudioPlayer::new(asset_server.load(sound)),
+            PlaybackSettings::DESPAWN.with_volume(Volume::Linear(0.4)),
+        ));
+    }
+}
+
 // Utility function to add footstep emitter to an entity
 pub fn add_footstep_emitter(commands: &mut Commands, entity: Entity) {
     commands.entity(entity).insert(FootstepEmitter::default());
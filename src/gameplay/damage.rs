@@ -0,0 +1,295 @@
+//! Health and damage pipeline
+//!
+//! Everything that hurts writes a [`DamageEvent`]: hitscan weapons, explosions,
+//! fall damage and props smashing into each other. [`apply_damage`] runs the
+//! event through hit group scaling and armor, subtracts it from the target's
+//! [`Health`], pushes dynamic bodies and sends a [`Death`] event when the
+//! target runs out of health.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use avian3d::prelude::*;
+
+use crate::debug::cheats::GodMode;
+
+pub struct DamagePlugin;
+
+impl Plugin for DamagePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ArmorSettings>()
+            .add_event::<DamageEvent>()
+            .add_event::<Death>()
+            .add_systems(Update, apply_damage.in_set(DamageSystems));
+    }
+}
+
+/// Damage is applied in this set; run after it to react to [`Death`] in the same frame
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DamageSystems;
+
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+pub struct Health {
+    pub current: f32,
+    pub maximum: f32,
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self::new(100.0)
+    }
+}
+
+impl Health {
+    pub fn new(maximum: f32) -> Self {
+        Self {
+            current: maximum,
+            maximum,
+        }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.0
+    }
+
+    /// Heals up to the maximum and returns how much was actually gained
+    pub fn heal(&mut self, amount: f32) -> f32 {
+        let old = self.current;
+        self.current = (self.current + amount).min(self.maximum);
+        self.current - old
+    }
+}
+
+/// Armor points that soak up part of incoming damage (HEV suit / kevlar)
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+pub struct Armor {
+    pub current: f32,
+    pub maximum: f32,
+}
+
+impl Default for Armor {
+    fn default() -> Self {
+        Self {
+            current: 0.0,
+            maximum: 100.0,
+        }
+    }
+}
+
+/// How armor splits damage between itself and health
+#[derive(Resource, Clone, Copy)]
+pub struct ArmorSettings {
+    /// Share of the damage that still reaches health while armor lasts
+    pub ratio: f32,
+    /// Damage absorbed per armor point is `1.0 / bonus`
+    pub bonus: f32,
+}
+
+impl Default for ArmorSettings {
+    fn default() -> Self {
+        // HL: Rüstung nimmt 80% des Schadens, jeder Punkt schluckt 2 Schaden
+        Self {
+            ratio: 0.2,
+            bonus: 0.5,
+        }
+    }
+}
+
+impl Armor {
+    /// Absorbs part of `amount` and returns the damage that still reaches health
+    pub fn absorb(&mut self, amount: f32, settings: &ArmorSettings) -> f32 {
+        if self.current <= 0.0 {
+            return amount;
+        }
+
+        let mut health_damage = amount * settings.ratio;
+        let mut armor_cost = (amount - health_damage) * settings.bonus;
+
+        // Nicht genug Rüstung: Rest geht auf die Gesundheit
+        if armor_cost > self.current {
+            armor_cost = self.current;
+            health_damage = amount - armor_cost / settings.bonus.max(f32::EPSILON);
+        }
+
+        self.current -= armor_cost;
+        health_damage
+    }
+
+    /// Adds armor up to the maximum and returns how much was actually gained
+    pub fn charge(&mut self, amount: f32) -> f32 {
+        let old = self.current;
+        self.current = (self.current + amount).min(self.maximum);
+        self.current - old
+    }
+}
+
+/// HL `DMG_*` style damage categories
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum DamageType {
+    #[default]
+    Generic,
+    Bullet,
+    Blast,
+    Fall,
+    Crush,
+    Slash,
+    Club,
+}
+
+impl DamageType {
+    /// Fall damage goes straight through armor like in HL
+    pub fn ignores_armor(self) -> bool {
+        matches!(self, Self::Fall)
+    }
+}
+
+/// Body region that was hit
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum HitGroup {
+    #[default]
+    Generic,
+    Head,
+    Chest,
+    Stomach,
+    LeftArm,
+    RightArm,
+    LeftLeg,
+    RightLeg,
+}
+
+impl HitGroup {
+    /// Damage multiplier (HL skill.cfg defaults)
+    pub fn multiplier(self) -> f32 {
+        match self {
+            Self::Head => 3.0,
+            _ => 1.0,
+        }
+    }
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct DamageEvent {
+    pub target: Entity,
+    /// Who caused the damage (the shooting player)
+    pub attacker: Option<Entity>,
+    /// What dealt it (weapon, grenade, barrel)
+    pub inflictor: Option<Entity>,
+    pub amount: f32,
+    pub damage_type: DamageType,
+    pub point: Vec3,
+    pub hit_group: HitGroup,
+    /// Impulse applied to dynamic targets
+    pub force: Vec3,
+}
+
+impl DamageEvent {
+    pub fn new(target: Entity, amount: f32, damage_type: DamageType, point: Vec3) -> Self {
+        Self {
+            target,
+            attacker: None,
+            inflictor: None,
+            amount,
+            damage_type,
+            point,
+            hit_group: HitGroup::Generic,
+            force: Vec3::ZERO,
+        }
+    }
+}
+
+/// Sent once when an entity's health drops to zero
+#[derive(Event, Clone, Copy, Debug)]
+pub struct Death {
+    pub entity: Entity,
+    pub attacker: Option<Entity>,
+    pub inflictor: Option<Entity>,
+    pub damage_type: DamageType,
+    pub point: Vec3,
+    pub force: Vec3,
+}
+
+/// Resolves a hit collider to the entity that owns the health: hit group
+/// colliders (e.g. the head hitbox) forward damage to their parent
+pub fn resolve_hit(
+    entity: Entity,
+    hit_groups: &Query<(&HitGroup, &ChildOf)>,
+) -> (Entity, HitGroup) {
+    match hit_groups.get(entity) {
+        Ok((hit_group, child_of)) => (child_of.parent(), *hit_group),
+        Err(_) => (entity, HitGroup::Generic),
+    }
+}
+
+fn apply_damage(
+    mut commands: Commands,
+    mut damage_events: EventReader<DamageEvent>,
+    mut death_events: EventWriter<Death>,
+    armor_settings: Res<ArmorSettings>,
+    mut targets: Query<(&mut Health, Option<&mut Armor>, Has<GodMode>)>,
+    mut bodies: Query<(&RigidBody, Option<&mut ExternalImpulse>)>,
+) {
+    // Impulse für Körper ohne ExternalImpulse, am Ende einmal eingefügt
+    let mut new_impulses: HashMap<Entity, Vec3> = HashMap::new();
+
+    for damage in damage_events.read() {
+        if damage.force != Vec3::ZERO {
+            // Mehrere Treffer im selben Frame (Schrot, Explosion) addieren sich
+            match bodies.get_mut(damage.target) {
+                Ok((body, Some(mut impulse))) if body.is_dynamic() => {
+                    impulse.apply_impulse(damage.force);
+                }
+                Ok((body, None)) if body.is_dynamic() => {
+                    *new_impulses.entry(damage.target).or_default() += damage.force;
+                }
+                _ => {}
+            }
+        }
+
+        let Ok((mut health, armor, god_mode)) = targets.get_mut(damage.target) else {
+            continue;
+        };
+        if god_mode || health.is_dead() {
+            continue;
+        }
+
+        let mut amount = damage.amount * damage.hit_group.multiplier();
+        if let Some(mut armor) = armor {
+            if !damage.damage_type.ignores_armor() {
+                amount = armor.absorb(amount, &armor_settings);
+            }
+        }
+
+        health.current = (health.current - amount).max(0.0);
+        debug!("{:?} took {:.0} {:?} damage ({:?}) - Health: {:.0}/{:.0}",
+            damage.target, amount, damage.damage_type, damage.hit_group, health.current, health.maximum);
+
+        if health.is_dead() {
+            death_events.write(Death {
+                entity: damage.target,
+                attacker: damage.attacker,
+                inflictor: damage.inflictor,
+                damage_type: damage.damage_type,
+                point: damage.point,
+                force: damage.force,
+            });
+            info!("{:?} died ({:?})", damage.target, damage.damage_type);
+        }
+    }
+
+    for (entity, impulse) in new_impulses {
+        add_impulse(&mut commands, entity, impulse);
+    }
+}
+
+/// Adds to the impulse already queued for `entity` this step instead of
+/// replacing it, inserting the component if the body has none yet
+pub fn add_impulse(commands: &mut Commands, entity: Entity, impulse: Vec3) {
+    commands
+        .entity(entity)
+        .entry::<ExternalImpulse>()
+        .and_modify(move |mut external| {
+            external.apply_impulse(impulse);
+        })
+        .or_insert(ExternalImpulse::new(impulse));
+}
@@ -0,0 +1,188 @@
+//! Explosions and radial damage
+//!
+//! Anything that blows up writes an [`Explosion`] event: explosive props,
+//! grenades and `env_explosion` map entities (right away, or when a
+//! [`FireTarget`] names their targetname). Every player or prop inside the
+//! radius with a clear line of sight to the origin gets a blast
+//! [`DamageEvent`] scaled by distance, which also pushes dynamic bodies away.
+
+use bevy::prelude::*;
+use avian3d::prelude::*;
+
+use crate::core::physics::{world_filter, GameLayer};
+use crate::gameplay::damage::{DamageEvent, DamageSystems, DamageType};
+use crate::gameplay::interaction::FireTarget;
+
+pub struct ExplosionPlugin;
+
+impl Plugin for ExplosionPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<Explosion>()
+            .add_systems(Update, (
+                trigger_env_explosions,
+                apply_explosions,
+            ).chain().before(DamageSystems));
+    }
+}
+
+/// Radial damage and push around `origin`
+#[derive(Event, Clone, Copy, Debug)]
+pub struct Explosion {
+    pub origin: Vec3,
+    pub radius: f32,
+    /// Damage at the center
+    pub damage: f32,
+    /// Exponent of the distance falloff: 1.0 = linear like HL, 0.0 = full damage in the whole radius
+    pub falloff: f32,
+    /// Entity that exploded, never hit by its own blast
+    pub source: Option<Entity>,
+    pub attacker: Option<Entity>,
+}
+
+impl Explosion {
+    /// HL explosion from a magnitude: damage = magnitude, radius = 2.5 * magnitude units
+    pub fn from_magnitude(origin: Vec3, magnitude: f32) -> Self {
+        Self {
+            origin,
+            radius: magnitude * 2.5 * 0.0254,
+            damage: magnitude,
+            falloff: 1.0,
+            source: None,
+            attacker: None,
+        }
+    }
+
+    pub fn damage_at(&self, distance: f32) -> f32 {
+        if distance >= self.radius {
+            return 0.0;
+        }
+        self.damage * (1.0 - distance / self.radius.max(f32::EPSILON)).powf(self.falloff)
+    }
+}
+
+/// Impulse per point of damage a dynamic body receives
+const IMPULSE_PER_DAMAGE: f32 = 0.5;
+
+/// `env_explosion` map entity
+#[derive(Component)]
+pub struct EnvExplosion {
+    pub magnitude: f32,
+    /// Entities with a targetname wait to be triggered, all others go off when spawned
+    pub targetname: Option<String>,
+    /// Spawnflag 1: visual only, no damage or push
+    pub no_damage: bool,
+    /// Spawnflag 2: stays around after going off and can be triggered again
+    pub repeatable: bool,
+}
+
+impl EnvExplosion {
+    pub fn from_map(properties: &std::collections::HashMap<String, String>) -> Self {
+        let spawnflags = properties
+            .get("spawnflags")
+            .and_then(|value| value.parse::<u32>().ok())
+            .unwrap_or(0);
+
+        Self {
+            magnitude: properties
+                .get("iMagnitude")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(100.0),
+            targetname: properties.get("targetname").cloned(),
+            no_damage: spawnflags & 1 != 0,
+            repeatable: spawnflags & 2 != 0,
+        }
+    }
+
+    pub fn explosion(&self, origin: Vec3) -> Explosion {
+        let mut explosion = Explosion::from_magnitude(origin, self.magnitude);
+        if self.no_damage {
+            explosion.damage = 0.0;
+        }
+        explosion
+    }
+}
+
+fn trigger_env_explosions(
+    mut commands: Commands,
+    mut explosion_events: EventWriter<Explosion>,
+    mut fire_events: EventReader<FireTarget>,
+    spawned: Query<(&Transform, &EnvExplosion), Added<EnvExplosion>>,
+    all: Query<(Entity, &Transform, &EnvExplosion)>,
+) {
+    for (transform, env_explosion) in spawned.iter() {
+        if env_explosion.targetname.is_none() {
+            explosion_events.write(env_explosion.explosion(transform.translation));
+        }
+    }
+
+    for fire in fire_events.read() {
+        for (entity, transform, env_explosion) in all.iter() {
+            if env_explosion.targetname.as_deref() != Some(fire.targetname.as_str()) {
+                continue;
+            }
+            explosion_events.write(Explosion {
+                attacker: fire.activator,
+                ..env_explosion.explosion(transform.translation)
+            });
+            if !env_explosion.repeatable {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+}
+
+fn apply_explosions(
+    mut explosion_events: EventReader<Explosion>,
+    spatial_query: SpatialQuery,
+    mut damage_events: EventWriter<DamageEvent>,
+    targets: Query<&GlobalTransform>,
+    mut debug_rays: ResMut<crate::core::physics::DebugRays>,
+    time: Res<Time>,
+) {
+    for explosion in explosion_events.read() {
+        let mut filter = SpatialQueryFilter::from_mask([GameLayer::Player, GameLayer::Prop]);
+        if let Some(source) = explosion.source {
+            filter = filter.with_excluded_entities([source]);
+        }
+
+        let hits = spatial_query.shape_intersections(
+            &Collider::sphere(explosion.radius),
+            explosion.origin,
+            Quat::IDENTITY,
+            &filter,
+        );
+
+        for target in hits {
+            let Ok(target_transform) = targets.get(target) else {
+                continue;
+            };
+
+            let to_target = target_transform.translation() - explosion.origin;
+            let distance = to_target.length();
+
+            // Sichtlinie: Wände dazwischen schlucken die Explosion
+            if let Ok(direction) = Dir3::new(to_target) {
+                if let Some(blocker) = spatial_query.cast_ray(explosion.origin, direction, distance, true, &world_filter()) {
+                    debug_rays.add(time.elapsed_secs(), explosion.origin, explosion.origin + direction * blocker.distance, Some(blocker.normal), Color::srgb(0.5, 0.5, 0.5));
+                    continue;
+                }
+            }
+
+            let damage = explosion.damage_at(distance);
+            if damage <= 0.0 {
+                continue;
+            }
+            debug_rays.add(time.elapsed_secs(), explosion.origin, target_transform.translation(), None, Color::srgb(1.0, 0.5, 0.0));
+
+            damage_events.write(DamageEvent {
+                attacker: explosion.attacker,
+                inflictor: explosion.source,
+                force: to_target.normalize_or(Vec3::Y) * damage * IMPULSE_PER_DAMAGE,
+                ..DamageEvent::new(target, damage, DamageType::Blast, target_transform.translation())
+            });
+        }
+
+        info!("Explosion at {:?}: {:.0} damage in {:.1} m", explosion.origin, explosion.damage, explosion.radius);
+    }
+}
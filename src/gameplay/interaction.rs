@@ -1,14 +1,30 @@
+use std::collections::HashSet;
+
 use bevy::prelude::*;
+use avian3d::prelude::*;
+
+use crate::core::fps_controller::{CmdButtons, FpsController, PlayerCmd};
+use crate::core::physics::{DynamicCollider, GameLayer};
+use crate::gameplay::damage::{Armor, DamageEvent, DamageType, Health};
+use crate::gameplay::weapons::{AmmoType, WeaponPickup};
 
 pub struct InteractionPlugin;
 
 impl Plugin for InteractionPlugin {
     fn build(&self, app: &mut App) {
         app
+            .init_resource::<FallDamageSettings>()
+            .init_resource::<CarrySettings>()
+            .add_event::<PlayerUse>()
+            .add_event::<FireTarget>()
             .add_systems(Startup, setup_interaction_system)
             .add_systems(Update, (
-                handle_interaction_input,
+                // Ein Druck auf Use hat genau ein Ziel, die Verbraucher laufen danach
+                resolve_use,
+                (handle_carry_input, handle_interaction_input).after(resolve_use),
+                hold_carried_objects,
                 update_interaction_prompts,
+                apply_fall_damage,
             ));
     }
 }
@@ -33,32 +49,137 @@ pub struct HealthPack {
 
 #[derive(Component)]
 pub struct AmmoPack {
+    pub ammo_type: AmmoType,
     pub ammo_amount: u32,
 }
 
+/// Suit battery or kevlar vest
 #[derive(Component)]
-pub struct PlayerHealth {
-    pub current: f32,
-    pub maximum: f32,
+pub struct ArmorPack {
+    pub armor_amount: f32,
 }
 
-#[derive(Clone, Debug)]
-pub enum InteractionType {
-    HealthPack,
-    AmmoPack,
-    Button,
-    Door,
+/// HL suit battery charge (sk_battery)
+pub const BATTERY_ARMOR: f32 = 15.0;
+/// CS kevlar vest
+pub const KEVLAR_ARMOR: f32 = 100.0;
+
+/// Half-Life style fall damage curve: no damage up to `safe_speed`, then linear
+/// up to the full 100 HP at `fatal_speed`
+#[derive(Resource)]
+pub struct FallDamageSettings {
+    pub enabled: bool,
+    pub safe_speed: f32,
+    pub fatal_speed: f32,
+}
+
+impl Default for FallDamageSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            // HL: 580 bzw. 1024 units/s, auf unsere Gravitation (12 m/s², max. 25 m/s) umgerechnet
+            safe_speed: 13.0,
+            fatal_speed: 24.0,
+        }
+    }
+}
+
+impl FallDamageSettings {
+    pub fn damage_for_speed(&self, speed: f32) -> f32 {
+        if !self.enabled || speed <= self.safe_speed {
+            return 0.0;
+        }
+        (speed - self.safe_speed) * 100.0 / (self.fatal_speed - self.safe_speed).max(f32::EPSILON)
+    }
+}
+
+/// Limits and tuning for picking up physics objects with Use
+#[derive(Resource)]
+pub struct CarrySettings {
+    /// Heaviest object (kg) the player can lift
+    pub max_mass: f32,
+    pub reach: f32,
+    pub hold_distance: f32,
+    /// Spring stiffness pulling the object to the hold point (1/s)
+    pub stiffness: f32,
+    pub max_speed: f32,
+    /// The object is dropped when it gets stuck this far from the hold point
+    pub break_distance: f32,
+    pub throw_impulse: f32,
 }
 
-impl Default for PlayerHealth {
+impl Default for CarrySettings {
     fn default() -> Self {
         Self {
-            current: 100.0,
-            maximum: 100.0,
+            max_mass: 35.0,
+            reach: 2.0,
+            hold_distance: 1.5,
+            stiffness: 15.0,
+            max_speed: 12.0,
+            break_distance: 1.5,
+            throw_impulse: 8.0,
         }
     }
 }
 
+/// Weapon pickups are used from this close
+pub const WEAPON_PICKUP_RANGE: f32 = 2.0;
+
+/// What one press of Use does. [`resolve_use`] picks a single target per
+/// press, so carrying, items and weapon pickups never react to the same one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UseTarget {
+    /// Put down the carried object
+    Drop,
+    /// Pick up the light prop under the crosshair
+    Carry(Entity),
+    /// Closest item or weapon pickup in range
+    Item(Entity),
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct PlayerUse {
+    pub player: Entity,
+    pub target: UseTarget,
+}
+
+/// Fires this target (HL `target` key) when used
+#[derive(Component, Clone, Debug)]
+pub struct FiresTarget(pub String);
+
+/// HL `FireTargets`: every entity with this targetname reacts
+#[derive(Event, Clone, Debug)]
+pub struct FireTarget {
+    pub targetname: String,
+    pub activator: Option<Entity>,
+}
+
+/// Player is holding `entity` in front of the camera
+#[derive(Component)]
+pub struct Carrying {
+    pub entity: Entity,
+}
+
+/// The Attack press that threw a carried object, weapons ignore Attack until
+/// it is released so one click never both throws and fires
+#[derive(Component)]
+pub struct AttackConsumed;
+
+/// Object is being carried by `holder`
+#[derive(Component)]
+pub struct Carried {
+    pub holder: Entity,
+}
+
+#[derive(Clone, Debug)]
+pub enum InteractionType {
+    HealthPack,
+    AmmoPack,
+    Armor,
+    Button,
+    Door,
+}
+
 fn setup_interaction_system(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -89,7 +210,8 @@ fn setup_interaction_system(
         &mut meshes,
         &mut materials,
         Vec3::new(-3.0, 0.5, -3.0),
-        30,
+        AmmoType::NineMm,
+        34,
     );
 
     spawn_ammo_pack(
@@ -97,12 +219,22 @@ fn setup_interaction_system(
         &mut meshes,
         &mut materials,
         Vec3::new(3.0, 0.5, -3.0),
+        AmmoType::FiveFiveSix,
         60,
     );
 
+    // Armor
+    spawn_armor_pack(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        Vec3::new(0.0, 0.5, 4.0),
+        BATTERY_ARMOR,
+    );
+
     // Spawn interaction prompt UI (initially hidden)
     commands.spawn((
-        Text::new("Press E to interact"),
+        Text::new("Press F to interact"),
         Node {
             position_type: PositionType::Absolute,
             top: Val::Percent(20.0),
@@ -123,7 +255,7 @@ fn setup_interaction_system(
     ));
 }
 
-fn spawn_health_pack(
+pub(crate) fn spawn_health_pack(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<StandardMaterial>>,
@@ -138,6 +270,10 @@ fn spawn_health_pack(
             ..default()
         })),
         Transform::from_translation(position),
+        RigidBody::Static,
+        Collider::cuboid(0.5, 0.3, 0.5),
+        Sensor,
+        GameLayer::Pickup.layers(),
         Interactable {
             prompt_text: format!("Health Pack (+{} HP)", heal_amount),
             interaction_range: 2.0,
@@ -147,11 +283,12 @@ fn spawn_health_pack(
     ));
 }
 
-fn spawn_ammo_pack(
+pub(crate) fn spawn_ammo_pack(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<StandardMaterial>>,
     position: Vec3,
+    ammo_type: AmmoType,
     ammo_amount: u32,
 ) {
     commands.spawn((
@@ -162,81 +299,203 @@ fn spawn_ammo_pack(
             ..default()
         })),
         Transform::from_translation(position),
+        RigidBody::Static,
+        Collider::cuboid(0.4, 0.2, 0.6),
+        Sensor,
+        GameLayer::Pickup.layers(),
         Interactable {
-            prompt_text: format!("Ammo Pack (+{} rounds)", ammo_amount),
+            prompt_text: format!("Ammo Pack (+{} {})", ammo_amount, ammo_type.name()),
             interaction_range: 2.0,
             interaction_type: InteractionType::AmmoPack,
         },
-        AmmoPack { ammo_amount },
+        AmmoPack { ammo_type, ammo_amount },
+    ));
+}
+
+pub(crate) fn spawn_armor_pack(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    position: Vec3,
+    armor_amount: f32,
+) {
+    let (name, size) = if armor_amount >= KEVLAR_ARMOR {
+        ("Kevlar Vest", Vec3::new(0.5, 0.15, 0.6))
+    } else {
+        ("Battery", Vec3::new(0.2, 0.35, 0.2))
+    };
+
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::from_size(size))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::srgb(0.2, 0.4, 0.9),
+            emissive: Color::srgb(0.1, 0.15, 0.4).into(),
+            ..default()
+        })),
+        Transform::from_translation(position),
+        RigidBody::Static,
+        Collider::cuboid(size.x, size.y, size.z),
+        Sensor,
+        GameLayer::Pickup.layers(),
+        Interactable {
+            prompt_text: format!("{} (+{} armor)", name, armor_amount),
+            interaction_range: 2.0,
+            interaction_type: InteractionType::Armor,
+        },
+        ArmorPack { armor_amount },
+    ));
+}
+
+/// `func_button`: a panel that fires its target when used
+pub(crate) fn spawn_button(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    position: Vec3,
+    target: Option<String>,
+) {
+    let mut button = commands.spawn((
+        Mesh3d(meshes.add(Cuboid::new(0.3, 0.3, 0.1))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::srgb(0.7, 0.15, 0.1),
+            emissive: Color::srgb(0.3, 0.05, 0.0).into(),
+            ..default()
+        })),
+        Transform::from_translation(position),
+        RigidBody::Static,
+        Collider::cuboid(0.3, 0.3, 0.1),
+        GameLayer::World.layers(),
+        Interactable {
+            prompt_text: "Press button".to_string(),
+            interaction_range: 1.5,
+            interaction_type: InteractionType::Button,
+        },
+        Name::new("func_button"),
     ));
+    if let Some(target) = target {
+        button.insert(FiresTarget(target));
+    }
+}
+
+/// Turns the rising edge of [`CmdButtons::USE`] into one [`PlayerUse`]:
+/// drop what is carried, else pick up the prop under the crosshair, else use
+/// the closest item or weapon pickup in range
+pub(crate) fn resolve_use(
+    settings: Res<CarrySettings>,
+    spatial_query: SpatialQuery,
+    mut use_events: EventWriter<PlayerUse>,
+    player_query: Query<(Entity, &Transform, &PlayerCmd, Has<Carrying>), With<FpsController>>,
+    carryable_query: Query<&ComputedMass, (With<DynamicCollider>, Without<Carried>)>,
+    item_query: Query<(Entity, &Transform, Option<&Interactable>), (Or<(With<Interactable>, With<WeaponPickup>)>, Without<FpsController>)>,
+    mut held: Local<HashSet<Entity>>,
+) {
+    for (player_entity, player_transform, cmd, carrying) in player_query.iter() {
+        let pressed = cmd.buttons.contains(CmdButtons::USE);
+        let was_pressed = if pressed { !held.insert(player_entity) } else { !held.remove(&player_entity) };
+        if !pressed || was_pressed {
+            continue;
+        }
+
+        let target = if carrying {
+            Some(UseTarget::Drop)
+        } else if let Some(entity) = find_carryable(&spatial_query, player_entity, player_transform, &settings, &carryable_query) {
+            Some(UseTarget::Carry(entity))
+        } else {
+            item_query
+                .iter()
+                .map(|(entity, transform, interactable)| {
+                    let range = interactable.map_or(WEAPON_PICKUP_RANGE, |interactable| interactable.interaction_range);
+                    (entity, player_transform.translation.distance(transform.translation), range)
+                })
+                .filter(|(_, distance, range)| distance <= range)
+                .min_by(|(_, a, _), (_, b, _)| a.total_cmp(b))
+                .map(|(entity, _, _)| UseTarget::Item(entity))
+        };
+
+        if let Some(target) = target {
+            use_events.write(PlayerUse { player: player_entity, target });
+        }
+    }
 }
 
 fn handle_interaction_input(
     mut commands: Commands,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut player_query: Query<(&Transform, &mut PlayerHealth, &mut crate::gameplay::weapons::PlayerInventory), With<crate::core::fps_controller::FpsController>>,
-    mut interactable_query: Query<(Entity, &Transform, &Interactable, Option<&HealthPack>, Option<&AmmoPack>), Without<crate::core::fps_controller::FpsController>>,
-    mut weapon_query: Query<&mut crate::gameplay::weapons::Weapon>,
+    mut use_events: EventReader<PlayerUse>,
+    mut player_query: Query<(&mut Health, Option<&mut Armor>, &mut crate::gameplay::weapons::PlayerInventory), With<FpsController>>,
+    interactable_query: Query<(&Interactable, Option<&HealthPack>, Option<&AmmoPack>, Option<&ArmorPack>, Option<&FiresTarget>)>,
+    mut fire_events: EventWriter<FireTarget>,
 ) {
-    if !keyboard_input.just_pressed(KeyCode::KeyE) {
-        return;
-    }
+    for event in use_events.read() {
+        let UseTarget::Item(entity) = event.target else {
+            continue;
+        };
+        let Ok((interactable, health_pack, ammo_pack, armor_pack, fires_target)) = interactable_query.get(entity) else {
+            continue;
+        };
+        let Ok((mut player_health, mut armor, mut inventory)) = player_query.get_mut(event.player) else {
+            continue;
+        };
 
-    for (player_transform, mut player_health, mut inventory) in player_query.iter_mut() {
-        for (entity, interactable_transform, interactable, health_pack, ammo_pack) in interactable_query.iter_mut() {
-            let distance = player_transform.translation.distance(interactable_transform.translation);
-            
-            if distance <= interactable.interaction_range {
-                match interactable.interaction_type {
-                    InteractionType::HealthPack => {
-                        if let Some(health_pack) = health_pack {
-                            if player_health.current < player_health.maximum {
-                                player_health.current = (player_health.current + health_pack.heal_amount)
-                                    .min(player_health.maximum);
-                                    
-                                info!("Used health pack! Health: {}/{}", 
-                                    player_health.current, player_health.maximum);
-                                
-                                commands.entity(entity).despawn();
-                            }
-                        }
-                    },
-                    InteractionType::AmmoPack => {
-                        if let Some(ammo_pack) = ammo_pack {
-                            // Add ammo to current weapon
-                            if let Some(weapon_entity) = inventory.held_weapon {
-                                if let Ok(mut weapon) = weapon_query.get_mut(weapon_entity) {
-                                    let old_ammo = weapon.ammo;
-                                    weapon.ammo = (weapon.ammo + ammo_pack.ammo_amount).min(weapon.max_ammo);
-                                    let gained = weapon.ammo - old_ammo;
-                                    
-                                    if gained > 0 {
-                                        info!("Picked up {} ammo for {}! Total: {}/{}", 
-                                            gained, weapon.name, weapon.ammo, weapon.max_ammo);
-                                        commands.entity(entity).despawn();
-                                    }
-                                }
-                            } else {
-                                info!("No weapon equipped to receive ammo!");
-                            }
-                        }
-                    },
-                    _ => {
-                        info!("Interacted with {}", interactable.prompt_text);
+        match interactable.interaction_type {
+            InteractionType::HealthPack => {
+                if let Some(health_pack) = health_pack {
+                    if player_health.heal(health_pack.heal_amount) > 0.0 {
+                        info!("Used health pack! Health: {}/{}", 
+                            player_health.current, player_health.maximum);
+                        
+                        commands.entity(entity).despawn();
+                    }
+                }
+            },
+            InteractionType::AmmoPack => {
+                if let Some(ammo_pack) = ammo_pack {
+                    // Geht in den Vorrat des Munitionstyps, egal welche Waffe gehalten wird
+                    let gained = inventory.give_ammo(ammo_pack.ammo_type, ammo_pack.ammo_amount);
+                    if gained > 0 {
+                        info!("Picked up {} {} rounds! Total: {}/{}",
+                            gained, ammo_pack.ammo_type.name(),
+                            inventory.ammo(ammo_pack.ammo_type), ammo_pack.ammo_type.max_carry());
+                        commands.entity(entity).despawn();
+                    }
+                }
+            },
+            InteractionType::Armor => {
+                if let (Some(armor_pack), Some(armor)) = (armor_pack, armor.as_mut()) {
+                    if armor.charge(armor_pack.armor_amount) > 0.0 {
+                        info!("Picked up armor! Armor: {}/{}", armor.current, armor.maximum);
+                        commands.entity(entity).despawn();
                     }
                 }
-                break;
+            },
+            _ => {
+                info!("Interacted with {}", interactable.prompt_text);
+                if let Some(FiresTarget(target)) = fires_target {
+                    fire_events.write(FireTarget {
+                        targetname: target.clone(),
+                        activator: Some(event.player),
+                    });
+                }
             }
         }
     }
 }
 
 fn update_interaction_prompts(
-    player_query: Query<&Transform, With<crate::core::fps_controller::FpsController>>,
-    interactable_query: Query<(Entity, &Transform, &Interactable), Without<crate::core::fps_controller::FpsController>>,
+    settings: Res<CarrySettings>,
+    spatial_query: SpatialQuery,
+    player_query: Query<(Entity, &Transform, Has<Carrying>), With<FpsController>>,
+    interactable_query: Query<(Entity, &Transform, &Interactable), Without<FpsController>>,
+    carryable_query: Query<&ComputedMass, (With<DynamicCollider>, Without<Carried>)>,
     mut prompt_query: Query<(&mut InteractionPrompt, &mut Visibility, &mut Text)>,
 ) {
-    for player_transform in player_query.iter() {
+    for (player_entity, player_transform, carrying) in player_query.iter() {
+        // Anvisiertes Objekt zum Aufheben hat Vorrang
+        let carry_target = if carrying {
+            None
+        } else {
+            find_carryable(&spatial_query, player_entity, player_transform, &settings, &carryable_query)
+        };
+
         let mut closest_interactable: Option<(Entity, &Interactable, f32)> = None;
         
         // Find the closest interactable within range
@@ -256,11 +515,16 @@ fn update_interaction_prompts(
         
         // Update interaction prompt
         for (mut prompt, mut visibility, mut text) in prompt_query.iter_mut() {
-            if let Some((entity, interactable, _)) = closest_interactable {
+            if let Some(entity) = carry_target {
+                prompt.target_entity = entity;
+                prompt.is_visible = true;
+                *visibility = Visibility::Visible;
+                text.0 = "Press F - Pick up".to_string();
+            } else if let Some((entity, interactable, _)) = closest_interactable.filter(|_| !carrying) {
                 prompt.target_entity = entity;
                 prompt.is_visible = true;
                 *visibility = Visibility::Visible;
-                text.0 = format!("Press E - {}", interactable.prompt_text);
+                text.0 = format!("Press F - {}", interactable.prompt_text);
             } else {
                 prompt.is_visible = false;
                 *visibility = Visibility::Hidden;
@@ -269,7 +533,139 @@ fn update_interaction_prompts(
     }
 }
 
+/// Light enough dynamic object the player is looking at, within reach
+fn find_carryable(
+    spatial_query: &SpatialQuery,
+    player_entity: Entity,
+    player_transform: &Transform,
+    settings: &CarrySettings,
+    carryable_query: &Query<&ComputedMass, (With<DynamicCollider>, Without<Carried>)>,
+) -> Option<Entity> {
+    let direction = Dir3::new(-player_transform.local_z().normalize()).ok()?;
+    let filter = SpatialQueryFilter::from_mask(GameLayer::Prop).with_excluded_entities([player_entity]);
+    let hit = spatial_query.cast_ray(player_transform.translation, direction, settings.reach, true, &filter)?;
+
+    let mass = carryable_query.get(hit.entity).ok()?;
+    (mass.value() <= settings.max_mass).then_some(hit.entity)
+}
+
+fn handle_carry_input(
+    mut commands: Commands,
+    settings: Res<CarrySettings>,
+    mut use_events: EventReader<PlayerUse>,
+    player_query: Query<(Entity, &Transform, &PlayerCmd, Option<&Carrying>, Has<AttackConsumed>), With<FpsController>>,
+    mut attack_held: Local<HashSet<Entity>>,
+) {
+    for event in use_events.read() {
+        let Ok((_, _, _, carrying, _)) = player_query.get(event.player) else {
+            continue;
+        };
+
+        match event.target {
+            UseTarget::Drop => {
+                if let Some(carrying) = carrying {
+                    release_carried(&mut commands, event.player, carrying.entity);
+                    info!("Dropped carried object");
+                }
+            }
+            UseTarget::Carry(target) => {
+                commands.entity(event.player).insert(Carrying { entity: target });
+                commands.entity(target).insert((
+                    Carried { holder: event.player },
+                    GravityScale(0.0),
+                    // Kollidiert weiter mit der Welt, schiebt aber nicht den Träger
+                    CollisionLayers::new(GameLayer::Prop, [GameLayer::World, GameLayer::Prop]),
+                ));
+                info!("Picked up object");
+            }
+            UseTarget::Item(_) => {}
+        }
+    }
+
+    for (player_entity, player_transform, cmd, carrying, consumed) in player_query.iter() {
+        let attacking = cmd.buttons.contains(CmdButtons::ATTACK);
+        let pressed = attacking && attack_held.insert(player_entity);
+        if !attacking {
+            attack_held.remove(&player_entity);
+            if consumed {
+                commands.entity(player_entity).remove::<AttackConsumed>();
+            }
+        }
+
+        if !pressed {
+            continue;
+        }
+        let Some(carrying) = carrying else {
+            continue;
+        };
+
+        // Werfen: ablegen und in Blickrichtung anstoßen, der Klick schießt nicht mehr
+        let direction = -player_transform.local_z().normalize();
+        release_carried(&mut commands, player_entity, carrying.entity);
+        crate::gameplay::damage::add_impulse(&mut commands, carrying.entity, direction * settings.throw_impulse);
+        commands.entity(player_entity).insert(AttackConsumed);
+        info!("Threw carried object");
+    }
+}
+
+fn release_carried(commands: &mut Commands, holder: Entity, carried: Entity) {
+    commands.entity(holder).remove::<Carrying>();
+    if let Ok(mut entity) = commands.get_entity(carried) {
+        entity
+            .remove::<Carried>()
+            .insert((GravityScale(1.0), GameLayer::Prop.layers()));
+    }
+}
+
+/// Pulls carried objects towards the hold point in front of the camera with a
+/// velocity spring, so the solver still resolves collisions with the world
+fn hold_carried_objects(
+    mut commands: Commands,
+    settings: Res<CarrySettings>,
+    player_query: Query<(Entity, &Transform, &Carrying), With<FpsController>>,
+    mut carried_query: Query<(&Transform, &mut LinearVelocity, &mut AngularVelocity), (With<Carried>, Without<FpsController>)>,
+) {
+    for (player_entity, player_transform, carrying) in player_query.iter() {
+        let Ok((transform, mut linear_velocity, mut angular_velocity)) = carried_query.get_mut(carrying.entity) else {
+            // Objekt wurde zerstört
+            commands.entity(player_entity).remove::<Carrying>();
+            continue;
+        };
+
+        let hold_point = player_transform.translation - player_transform.local_z() * settings.hold_distance;
+        let offset = hold_point - transform.translation;
+
+        if offset.length() > settings.break_distance {
+            release_carried(&mut commands, player_entity, carrying.entity);
+            info!("Carried object got stuck and was dropped");
+            continue;
+        }
+
+        linear_velocity.0 = (offset * settings.stiffness).clamp_length_max(settings.max_speed);
+        angular_velocity.0 *= 0.8;
+    }
+}
+
+fn apply_fall_damage(
+    mut landed_events: EventReader<crate::core::fps_controller::PlayerLanded>,
+    settings: Res<FallDamageSettings>,
+    player_query: Query<&Transform, With<Health>>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    for landed in landed_events.read() {
+        let damage = settings.damage_for_speed(landed.speed);
+        if damage <= 0.0 {
+            continue;
+        }
+
+        if let Ok(transform) = player_query.get(landed.entity) {
+            damage_events.write(DamageEvent::new(landed.entity, damage, DamageType::Fall, transform.translation));
+            info!("Fall damage: {:.0} (impact {:.1} m/s)", damage, landed.speed);
+        }
+    }
+}
+
 // Utility function to add player health to player entity
 pub fn add_player_health_to_player(commands: &mut Commands, player_entity: Entity) {
-    commands.entity(player_entity).insert(PlayerHealth::default());
+    commands.entity(player_entity).insert(Health::default());
 }
\ No newline at end of file
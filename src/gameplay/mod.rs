@@ -2,13 +2,31 @@
 //! 
 //! Contains game-specific logic and mechanics:
 //! - Weapon system and combat
-//! - Player interactions and health
+//! - Player interactions
+//! - Health, armor and the damage pipeline
+//! - Ragdolls on death
 //! - Audio and sound effects
+//! - Surface materials
+//! - Pushable, breakable and explosive props
+//! - Explosions and radial damage
+//! - Grenades, rockets and crossbow bolts
 
 pub mod weapons;
 pub mod interaction;
+pub mod damage;
+pub mod ragdoll;
 pub mod audio;
+pub mod surfaces;
+pub mod props;
+pub mod explosions;
+pub mod projectiles;
 
 pub use weapons::WeaponPlugin;
 pub use interaction::InteractionPlugin;
-pub use audio::AudioPlugin;
\ No newline at end of file
+pub use damage::DamagePlugin;
+pub use ragdoll::RagdollPlugin;
+pub use audio::AudioPlugin;
+pub use surfaces::SurfacePlugin;
+pub use props::PropsPlugin;
+pub use explosions::ExplosionPlugin;
+pub use projectiles::ProjectilePlugin;
\ No newline at end of file
@@ -0,0 +1,355 @@
+//! Physical projectiles
+//!
+//! Weapons whose definition has a `projectile` section fire a
+//! [`SpawnProjectile`] event instead of a hitscan ray. Projectiles are dynamic
+//! bodies with swept CCD so fast ones don't tunnel through thin walls:
+//! grenades bounce until their fuse runs out, rockets fly straight and blow
+//! up on contact, bolts hurt what they hit and stick in walls. Damage goes
+//! through the usual [`DamageEvent`] / [`Explosion`] pipeline. Until a
+//! projectile has left its shooter's collider it ignores the player layer, so
+//! it can't bump into the player who fired it.
+
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use serde::Deserialize;
+
+use crate::content::weapon_definitions::ProjectileDefinition;
+use crate::core::physics::{world_filter, GameLayer};
+use crate::gameplay::damage::{self, DamageEvent, DamageSystems, DamageType, HitGroup};
+use crate::gameplay::explosions::Explosion;
+use crate::gameplay::surfaces::{SurfaceImpact, SurfaceProperties};
+
+pub struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ProjectileSettings>()
+            .add_event::<SpawnProjectile>()
+            .add_systems(Update, (
+                spawn_projectiles,
+                release_from_owner,
+                steer_projectiles,
+                handle_projectile_contacts,
+                tick_projectiles,
+            ).chain().before(DamageSystems));
+    }
+}
+
+#[derive(Resource)]
+pub struct ProjectileSettings {
+    /// Seconds until a projectile that never hit anything is removed
+    pub max_lifetime: f32,
+    /// Seconds a bolt stays stuck in a wall
+    pub stuck_lifetime: f32,
+}
+
+impl Default for ProjectileSettings {
+    fn default() -> Self {
+        Self {
+            max_lifetime: 10.0,
+            stuck_lifetime: 10.0,
+        }
+    }
+}
+
+/// How a projectile flies and what it does on contact
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum ProjectileKind {
+    /// Bounces around and explodes after `fuse` seconds
+    Grenade { fuse: f32 },
+    /// Flies straight at constant speed and explodes on contact
+    Rocket,
+    /// Damages what it hits directly, sticks in the world
+    Bolt,
+}
+
+/// Fire a projectile, written by weapons
+#[derive(Event, Debug, Clone)]
+pub struct SpawnProjectile {
+    pub origin: Vec3,
+    pub direction: Dir3,
+    pub definition: ProjectileDefinition,
+    /// Direct hit damage
+    pub damage: f32,
+    /// Velocity of the shooter, added to the launch velocity
+    pub inherited_velocity: Vec3,
+    pub owner: Option<Entity>,
+    pub weapon: Option<Entity>,
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
+pub struct Projectile {
+    pub kind: ProjectileKind,
+    pub damage: f32,
+    /// Explosion magnitude on detonation, 0 = none
+    pub explosion_magnitude: f32,
+    /// Launch speed, kept constant for rockets
+    pub speed: f32,
+    pub owner: Option<Entity>,
+    pub weapon: Option<Entity>,
+    pub age: f32,
+    /// Position last frame, for finding the contact point
+    pub last_position: Vec3,
+}
+
+/// Still inside the shooter, collides with players once it's out
+#[derive(Component)]
+pub struct LeavingOwner {
+    pub owner: Entity,
+}
+
+/// A bolt stuck in a wall, removed after [`ProjectileSettings::stuck_lifetime`]
+#[derive(Component)]
+pub struct StuckProjectile {
+    pub age: f32,
+}
+
+fn spawn_projectiles(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut spawn_events: EventReader<SpawnProjectile>,
+) {
+    for event in spawn_events.read() {
+        let definition = &event.definition;
+        let radius = definition.radius;
+
+        let (mesh, color, restitution) = match definition.kind {
+            ProjectileKind::Grenade { .. } => (
+                meshes.add(Sphere::new(radius)),
+                Color::srgb(0.2, 0.3, 0.15),
+                0.45,
+            ),
+            ProjectileKind::Rocket => (
+                meshes.add(Cuboid::new(radius * 2.0, radius * 2.0, 0.6)),
+                Color::srgb(0.5, 0.5, 0.45),
+                0.0,
+            ),
+            ProjectileKind::Bolt => (
+                meshes.add(Cuboid::new(0.02, 0.02, 0.4)),
+                Color::srgb(0.4, 0.3, 0.2),
+                0.0,
+            ),
+        };
+
+        let velocity = event.direction * definition.speed + event.inherited_velocity;
+        let transform = Transform::from_translation(event.origin).looking_to(event.direction, Vec3::Y);
+
+        let mut projectile = commands.spawn((
+            Mesh3d(mesh),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: color,
+                ..default()
+            })),
+            transform,
+            RigidBody::Dynamic,
+            Collider::sphere(radius),
+            // Schnelle Projektile sonst durch dünne Wände
+            SweptCcd::default(),
+            LinearVelocity(velocity),
+            GravityScale(definition.gravity),
+            Restitution::new(restitution),
+            CollidingEntities::default(),
+            Projectile {
+                kind: definition.kind,
+                damage: event.damage,
+                explosion_magnitude: definition.explosion_magnitude,
+                speed: definition.speed,
+                owner: event.owner,
+                weapon: event.weapon,
+                age: 0.0,
+                last_position: event.origin,
+            },
+            Name::new(format!("Projectile {:?}", definition.kind)),
+        ));
+
+        // Erst mit Spielern kollidieren, wenn der Schütze verlassen ist
+        match event.owner {
+            Some(owner) => projectile.insert((
+                CollisionLayers::new(GameLayer::Projectile, [GameLayer::World, GameLayer::Prop]),
+                LeavingOwner { owner },
+            )),
+            None => projectile.insert(GameLayer::Projectile.layers()),
+        };
+
+        // Rakete und Bolzen zeigen immer in Flugrichtung
+        if !matches!(definition.kind, ProjectileKind::Grenade { .. }) {
+            projectile.insert(LockedAxes::ROTATION_LOCKED);
+        }
+    }
+}
+
+/// Gives projectiles their full collision layers once they no longer overlap
+/// the shooter's body or hitboxes
+fn release_from_owner(
+    mut commands: Commands,
+    spatial_query: SpatialQuery,
+    query: Query<(Entity, &LeavingOwner, &Collider, &Transform)>,
+    children_query: Query<&ChildOf>,
+) {
+    for (entity, leaving, collider, transform) in query.iter() {
+        let inside_owner = spatial_query
+            .shape_intersections(
+                collider,
+                transform.translation,
+                transform.rotation,
+                &SpatialQueryFilter::from_mask(GameLayer::Player),
+            )
+            .into_iter()
+            .any(|other| {
+                other == leaving.owner
+                    || children_query.get(other).is_ok_and(|child_of| child_of.parent() == leaving.owner)
+            });
+
+        if !inside_owner {
+            commands.entity(entity)
+                .remove::<LeavingOwner>()
+                .insert(GameLayer::Projectile.layers());
+        }
+    }
+}
+
+/// Rockets keep their speed, rockets and bolts face where they fly
+fn steer_projectiles(
+    mut query: Query<(&Projectile, &mut LinearVelocity, &mut Transform), Without<StuckProjectile>>,
+) {
+    for (projectile, mut velocity, mut transform) in query.iter_mut() {
+        if matches!(projectile.kind, ProjectileKind::Grenade { .. }) {
+            continue;
+        }
+        if projectile.kind == ProjectileKind::Rocket {
+            velocity.0 = velocity.0.normalize_or_zero() * projectile.speed;
+        }
+        if let Ok(direction) = Dir3::new(velocity.0) {
+            transform.look_to(direction, Vec3::Y);
+        }
+    }
+}
+
+fn handle_projectile_contacts(
+    mut commands: Commands,
+    spatial_query: SpatialQuery,
+    query: Query<(Entity, &Projectile, &Transform, &LinearVelocity, &CollidingEntities), Without<StuckProjectile>>,
+    children_query: Query<&ChildOf>,
+    hit_groups: Query<(&HitGroup, &ChildOf)>,
+    surface_query: Query<&SurfaceProperties>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut explosion_events: EventWriter<Explosion>,
+    mut impact_events: EventWriter<SurfaceImpact>,
+) {
+    for (entity, projectile, transform, velocity, colliding) in query.iter() {
+        // Eigener Körper und Kopf-Hitbox des Schützen zählen nicht
+        let is_owner = |other: Entity| {
+            Some(other) == projectile.owner
+                || children_query.get(other).is_ok_and(|child_of| Some(child_of.parent()) == projectile.owner)
+        };
+        let Some(&other) = colliding.iter().find(|&&other| !is_owner(other)) else {
+            continue;
+        };
+
+        match projectile.kind {
+            // Granaten prallen einfach ab
+            ProjectileKind::Grenade { .. } => {}
+            ProjectileKind::Rocket => {
+                detonate(&mut commands, &mut explosion_events, entity, projectile, transform.translation);
+            }
+            ProjectileKind::Bolt => {
+                // Kontaktpunkt über einen Strahl vom letzten Frame aus bestimmen
+                let travel = transform.translation - projectile.last_position;
+                let direction = Dir3::new(travel).or_else(|_| Dir3::new(velocity.0)).unwrap_or(Dir3::NEG_Z);
+                let hit = spatial_query.cast_ray(
+                    projectile.last_position,
+                    direction,
+                    travel.length() + 0.5,
+                    true,
+                    &SpatialQueryFilter::from_mask([GameLayer::World, GameLayer::Player, GameLayer::Prop]),
+                );
+                let (point, normal) = hit.map_or((transform.translation, -direction.as_vec3()), |hit| {
+                    (projectile.last_position + direction * hit.distance, hit.normal)
+                });
+
+                impact_events.write(SurfaceImpact {
+                    entity: other,
+                    point,
+                    normal,
+                    surface: surface_query.get(other).copied().unwrap_or_default(),
+                });
+
+                let (target, hit_group) = damage::resolve_hit(other, &hit_groups);
+                damage_events.write(DamageEvent {
+                    attacker: projectile.owner,
+                    inflictor: projectile.weapon,
+                    hit_group,
+                    force: direction * projectile.damage * 0.05,
+                    ..DamageEvent::new(target, projectile.damage, DamageType::Bullet, point)
+                });
+
+                if projectile.explosion_magnitude > 0.0 {
+                    detonate(&mut commands, &mut explosion_events, entity, projectile, point);
+                } else if spatial_query
+                    .cast_ray(projectile.last_position, direction, travel.length() + 0.5, true, &world_filter())
+                    .is_some_and(|hit| hit.entity == other)
+                {
+                    // In der Wand stecken bleiben
+                    commands.entity(entity)
+                        .remove::<(RigidBody, Collider, SweptCcd, LinearVelocity, CollidingEntities)>()
+                        .insert((
+                            Transform::from_translation(point - direction * 0.15).looking_to(direction, Vec3::Y),
+                            StuckProjectile { age: 0.0 },
+                        ));
+                } else {
+                    commands.entity(entity).despawn();
+                }
+            }
+        }
+    }
+}
+
+/// Fuses, lifetimes and the contact point bookkeeping
+fn tick_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<ProjectileSettings>,
+    mut explosion_events: EventWriter<Explosion>,
+    mut flying: Query<(Entity, &mut Projectile, &Transform), Without<StuckProjectile>>,
+    mut stuck: Query<(Entity, &mut StuckProjectile)>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, mut projectile, transform) in flying.iter_mut() {
+        projectile.age += dt;
+        projectile.last_position = transform.translation;
+
+        let fuse_done = matches!(projectile.kind, ProjectileKind::Grenade { fuse } if projectile.age >= fuse);
+        if fuse_done {
+            detonate(&mut commands, &mut explosion_events, entity, &projectile, transform.translation);
+        } else if projectile.age >= settings.max_lifetime {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    for (entity, mut stuck_projectile) in stuck.iter_mut() {
+        stuck_projectile.age += dt;
+        if stuck_projectile.age >= settings.stuck_lifetime {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn detonate(
+    commands: &mut Commands,
+    explosion_events: &mut EventWriter<Explosion>,
+    entity: Entity,
+    projectile: &Projectile,
+    origin: Vec3,
+) {
+    if projectile.explosion_magnitude > 0.0 {
+        explosion_events.write(Explosion {
+            source: Some(entity),
+            attacker: projectile.owner,
+            ..Explosion::from_magnitude(origin, projectile.explosion_magnitude)
+        });
+    }
+    commands.entity(entity).despawn();
+}
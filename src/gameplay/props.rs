@@ -0,0 +1,405 @@
+//! Physics props
+//!
+//! Pushable, breakable and explosive objects, spawned from `func_breakable` /
+//! `func_pushable` map entities or placed by code. Breakables take damage when
+//! shot or smashed, break into gibs and can drop an item; explosive props set
+//! off an [`Explosion`] when they break.
+
+use bevy::prelude::*;
+use avian3d::prelude::*;
+
+use crate::core::physics::{DynamicCollider, GameLayer, PhysicsObject, StaticCollider};
+use crate::gameplay::damage::{DamageEvent, DamageSystems, DamageType, Death, Health};
+use crate::gameplay::explosions::Explosion;
+use crate::gameplay::interaction;
+use crate::gameplay::surfaces::SurfaceMaterial;
+use crate::gameplay::weapons::{self, AmmoType};
+
+pub struct PropsPlugin;
+
+impl Plugin for PropsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Direkt nach dem Physik-Schritt, solange die Kontaktimpulse zum Aufprall gehören
+            .add_systems(FixedPostUpdate, smash_props.after(PhysicsSet::StepSimulation))
+            .add_systems(Update, (
+                break_props.after(DamageSystems),
+                update_gibs,
+            ));
+    }
+}
+
+/// Half-Life func_breakable materials
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PropMaterial {
+    Glass,
+    #[default]
+    Wood,
+    Metal,
+    Flesh,
+    Cinder,
+    CeilingTile,
+    Computer,
+    UnbreakableGlass,
+    Rocks,
+}
+
+impl PropMaterial {
+    /// Index of the `material` key of func_breakable
+    pub fn from_index(index: u32) -> Self {
+        match index {
+            0 => Self::Glass,
+            2 => Self::Metal,
+            3 => Self::Flesh,
+            4 => Self::Cinder,
+            5 => Self::CeilingTile,
+            6 => Self::Computer,
+            7 => Self::UnbreakableGlass,
+            8 => Self::Rocks,
+            _ => Self::Wood,
+        }
+    }
+
+    pub fn surface(self) -> SurfaceMaterial {
+        match self {
+            Self::Glass | Self::UnbreakableGlass => SurfaceMaterial::Glass,
+            Self::Wood => SurfaceMaterial::Wood,
+            Self::Metal => SurfaceMaterial::Metal,
+            Self::Flesh => SurfaceMaterial::Flesh,
+            Self::Cinder | Self::Rocks => SurfaceMaterial::Concrete,
+            Self::CeilingTile => SurfaceMaterial::Tile,
+            Self::Computer => SurfaceMaterial::Computer,
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            Self::Glass | Self::UnbreakableGlass => Color::srgba(0.6, 0.8, 0.9, 0.4),
+            Self::Wood => Color::srgb(0.55, 0.38, 0.2),
+            Self::Metal => Color::srgb(0.5, 0.5, 0.55),
+            Self::Flesh => Color::srgb(0.6, 0.1, 0.1),
+            Self::Cinder | Self::Rocks => Color::srgb(0.45, 0.45, 0.42),
+            Self::CeilingTile => Color::srgb(0.85, 0.85, 0.8),
+            Self::Computer => Color::srgb(0.3, 0.3, 0.35),
+        }
+    }
+}
+
+/// Item a breakable leaves behind
+#[derive(Clone, Copy, Debug)]
+pub enum PropDrop {
+    HealthPack(f32),
+    AmmoPack(AmmoType, u32),
+    /// Weapon definition id
+    Weapon(&'static str),
+}
+
+impl PropDrop {
+    /// Index of the `spawnobject` key of func_breakable (only items we have)
+    pub fn from_spawnobject(index: u32) -> Option<Self> {
+        match index {
+            2 => Some(Self::HealthPack(25.0)),
+            3 => Some(Self::Weapon("pistol")),
+            4 => Some(Self::AmmoPack(AmmoType::NineMm, 17)),
+            5 => Some(Self::Weapon("rifle")),
+            6 => Some(Self::AmmoPack(AmmoType::FiveFiveSix, 30)),
+            8 => Some(Self::Weapon("shotgun")),
+            9 => Some(Self::AmmoPack(AmmoType::Buckshot, 12)),
+            _ => None,
+        }
+    }
+}
+
+/// Breaks into gibs when its [`Health`] runs out
+#[derive(Component)]
+pub struct Breakable {
+    pub material: PropMaterial,
+    pub drop: Option<PropDrop>,
+    /// Impact speed (m/s) above which collisions hurt
+    pub smash_speed: f32,
+}
+
+impl Breakable {
+    pub fn new(material: PropMaterial) -> Self {
+        Self {
+            material,
+            drop: None,
+            smash_speed: 6.0,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Pushable;
+
+/// Explodes when the prop breaks
+#[derive(Component, Clone, Copy)]
+pub struct ExplosiveProp {
+    pub damage: f32,
+    pub radius: f32,
+}
+
+impl ExplosiveProp {
+    pub fn from_magnitude(magnitude: f32) -> Self {
+        let explosion = Explosion::from_magnitude(Vec3::ZERO, magnitude);
+        Self {
+            damage: explosion.damage,
+            radius: explosion.radius,
+        }
+    }
+}
+
+#[derive(Component)]
+struct Gib {
+    lifetime: Timer,
+}
+
+/// Description of a prop to spawn
+#[derive(Clone, Debug)]
+pub struct PropSettings {
+    pub size: Vec3,
+    pub material: PropMaterial,
+    pub dynamic: bool,
+    /// `None` or unbreakable material = can't be broken
+    pub health: Option<f32>,
+    pub drop: Option<PropDrop>,
+    pub explode_magnitude: f32,
+}
+
+impl Default for PropSettings {
+    fn default() -> Self {
+        Self {
+            size: Vec3::ONE,
+            material: PropMaterial::Wood,
+            dynamic: true,
+            health: None,
+            drop: None,
+            explode_magnitude: 0.0,
+        }
+    }
+}
+
+pub fn spawn_prop(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    transform: Transform,
+    settings: &PropSettings,
+) -> Entity {
+    let size = settings.size;
+    let surface = settings.material.surface().properties();
+    let color = settings.material.color();
+
+    let mut entity = commands.spawn((
+        Mesh3d(meshes.add(Cuboid::from_size(size))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: color,
+            alpha_mode: if color.alpha() < 1.0 { AlphaMode::Blend } else { AlphaMode::Opaque },
+            ..default()
+        })),
+        transform,
+        Collider::cuboid(size.x, size.y, size.z),
+        GameLayer::Prop.layers(),
+        surface,
+        Friction::new(surface.friction),
+        PhysicsObject,
+        Name::new("Prop"),
+    ));
+
+    if settings.dynamic {
+        entity.insert((RigidBody::Dynamic, DynamicCollider, Pushable));
+    } else {
+        entity.insert((RigidBody::Static, StaticCollider));
+    }
+
+    if let Some(health) = settings.health {
+        if settings.material != PropMaterial::UnbreakableGlass {
+            let mut breakable = Breakable::new(settings.material);
+            breakable.drop = settings.drop;
+            entity.insert((breakable, Health::new(health), CollisionEventsEnabled));
+        }
+    }
+
+    if settings.explode_magnitude > 0.0 {
+        entity.insert(ExplosiveProp::from_magnitude(settings.explode_magnitude));
+    }
+
+    entity.id()
+}
+
+/// Builds a prop from `func_breakable` / `func_pushable` key values
+pub fn prop_settings_from_map(classname: &str, properties: &std::collections::HashMap<String, String>) -> PropSettings {
+    let get_f32 = |key: &str| properties.get(key).and_then(|value| value.parse::<f32>().ok());
+    let get_u32 = |key: &str| properties.get(key).and_then(|value| value.parse::<u32>().ok());
+
+    let size = properties
+        .get("size")
+        .and_then(|value| {
+            let parts: Vec<f32> = value.split_whitespace().filter_map(|part| part.parse().ok()).collect();
+            (parts.len() == 3).then(|| Vec3::new(parts[0], parts[1], parts[2]))
+        })
+        .unwrap_or(Vec3::ONE);
+
+    let pushable = classname == "func_pushable";
+    let spawnflags = get_u32("spawnflags").unwrap_or(0);
+    // func_pushable ist nur mit Flag 128 zerbrechlich, func_breakable immer
+    let breakable = !pushable || spawnflags & 128 != 0;
+
+    PropSettings {
+        size,
+        material: PropMaterial::from_index(get_u32("material").unwrap_or(1)),
+        dynamic: pushable,
+        health: breakable.then(|| get_f32("health").unwrap_or(20.0)),
+        drop: get_u32("spawnobject").and_then(PropDrop::from_spawnobject),
+        explode_magnitude: get_f32("explodemagnitude").unwrap_or(0.0),
+    }
+}
+
+/// Damage from hitting or being hit by something, only on the first contact.
+/// The impact speed is the relative velocity along the contact normal, taken
+/// from the solver impulse since the velocities are already resolved by now.
+fn smash_props(
+    mut damage_events: EventWriter<DamageEvent>,
+    mut collision_events: EventReader<CollisionStarted>,
+    collisions: Collisions,
+    breakables: Query<(&Transform, &Breakable)>,
+    collider_of: Query<&ColliderOf>,
+    bodies: Query<(&RigidBody, &ComputedMass)>,
+) {
+    // Statische und kinematische Körper sind unendlich schwer
+    let inverse_mass = |collider: Entity| {
+        let body = collider_of.get(collider).map_or(collider, |collider_of| collider_of.body);
+        match bodies.get(body) {
+            Ok((RigidBody::Dynamic, mass)) => mass.inverse(),
+            _ => 0.0,
+        }
+    };
+
+    for CollisionStarted(first, second) in collision_events.read() {
+        let Some(contacts) = collisions.get(*first, *second) else {
+            continue;
+        };
+
+        // Reduzierte Masse: Impuls = μ · Relativgeschwindigkeit entlang der Normale
+        let inverse_masses = inverse_mass(*first) + inverse_mass(*second);
+        if inverse_masses <= 0.0 {
+            continue;
+        }
+        let impact = contacts.total_normal_impulse_magnitude() * inverse_masses;
+
+        for entity in [*first, *second] {
+            let Ok((transform, breakable)) = breakables.get(entity) else {
+                continue;
+            };
+            if impact > breakable.smash_speed {
+                damage_events.write(DamageEvent::new(
+                    entity,
+                    (impact - breakable.smash_speed) * 10.0,
+                    DamageType::Crush,
+                    transform.translation,
+                ));
+            }
+        }
+    }
+}
+
+fn break_props(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut explosion_events: EventWriter<Explosion>,
+    mut death_events: EventReader<Death>,
+    broken: Query<(&Transform, &Breakable, Option<&ExplosiveProp>, Option<&Collider>)>,
+) {
+    for death in death_events.read() {
+        let entity = death.entity;
+        let Ok((transform, breakable, explosive, collider)) = broken.get(entity) else {
+            continue;
+        };
+
+        let origin = transform.translation;
+        let half_extents = collider
+            .map(|collider| collider.aabb(Vec3::ZERO, Quat::IDENTITY).size() * 0.5)
+            .unwrap_or(Vec3::splat(0.5));
+
+        commands.entity(entity).despawn();
+        spawn_gibs(&mut commands, &mut meshes, &mut materials, origin, half_extents, breakable.material);
+
+        match breakable.drop {
+            Some(PropDrop::HealthPack(amount)) => {
+                interaction::spawn_health_pack(&mut commands, &mut meshes, &mut materials, origin, amount);
+            },
+            Some(PropDrop::AmmoPack(ammo_type, amount)) => {
+                interaction::spawn_ammo_pack(&mut commands, &mut meshes, &mut materials, origin, ammo_type, amount);
+            },
+            Some(PropDrop::Weapon(weapon_id)) => {
+                weapons::spawn_weapon_pickup(&mut commands, weapon_id, origin, 30);
+            },
+            None => {}
+        }
+
+        info!("Prop broke ({:?}) at {:?}", breakable.material, origin);
+
+        if let Some(explosive) = explosive {
+            explosion_events.write(Explosion {
+                origin,
+                radius: explosive.radius,
+                damage: explosive.damage,
+                falloff: 1.0,
+                source: Some(entity),
+                // Wer das Fass zerschossen hat, ist für die Explosion verantwortlich
+                attacker: death.attacker,
+            });
+        }
+    }
+}
+
+fn spawn_gibs(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    origin: Vec3,
+    half_extents: Vec3,
+    material: PropMaterial,
+) {
+    let gib_size = (half_extents.min_element() * 0.5).clamp(0.05, 0.25);
+    let mesh = meshes.add(Cuboid::from_length(gib_size));
+    let gib_material = materials.add(StandardMaterial {
+        base_color: material.color(),
+        ..default()
+    });
+
+    let count = 8;
+    for i in 0..count {
+        // Gleichmäßig verteilte Richtungen (Fibonacci-Kugel) statt Zufall
+        let y = 1.0 - (i as f32 + 0.5) / count as f32 * 2.0;
+        let radius = (1.0 - y * y).sqrt();
+        let angle = i as f32 * 2.399_963; // goldener Winkel
+        let direction = Vec3::new(angle.cos() * radius, y.abs(), angle.sin() * radius);
+
+        commands.spawn((
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(gib_material.clone()),
+            Transform::from_translation(origin + direction * half_extents),
+            RigidBody::Dynamic,
+            Collider::cuboid(gib_size, gib_size, gib_size),
+            GameLayer::Debris.layers(),
+            LinearVelocity(direction * 3.0),
+            Gib {
+                lifetime: Timer::from_seconds(6.0 + i as f32 * 0.25, TimerMode::Once),
+            },
+        ));
+    }
+}
+
+fn update_gibs(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut gibs: Query<(Entity, &mut Gib)>,
+) {
+    for (entity, mut gib) in gibs.iter_mut() {
+        if gib.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
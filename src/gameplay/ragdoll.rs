@@ -0,0 +1,333 @@
+//! Ragdolls
+//!
+//! When a character with a [`RagdollProfile`] dies, its model is swapped for
+//! one dynamic body per bone, connected by avian3d joints. The killing hit's
+//! force is applied to the bone closest to the hit point. Ragdolls fade out
+//! after [`RagdollSettings::fade_after`] seconds, and the oldest ones are
+//! faded early when more than [`RagdollSettings::max_ragdolls`] exist.
+
+use bevy::prelude::*;
+use avian3d::prelude::*;
+
+use crate::content::assets::CharacterModel;
+use crate::core::physics::GameLayer;
+use crate::gameplay::damage::{DamageSystems, Death};
+
+pub struct RagdollPlugin;
+
+impl Plugin for RagdollPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<RagdollSettings>()
+            .add_systems(Update, (
+                spawn_ragdolls.after(DamageSystems),
+                fade_ragdolls,
+            ).chain());
+    }
+}
+
+#[derive(Resource)]
+pub struct RagdollSettings {
+    /// `false` = dead characters keep their model instead of turning into a ragdoll
+    pub enabled: bool,
+    /// Seconds until a ragdoll starts fading
+    pub fade_after: f32,
+    pub fade_duration: f32,
+    pub max_ragdolls: usize,
+    /// Multiplier on the killing force, bullet forces alone barely move a body
+    pub impulse_scale: f32,
+}
+
+impl Default for RagdollSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            fade_after: 10.0,
+            fade_duration: 2.0,
+            max_ragdolls: 8,
+            impulse_scale: 4.0,
+        }
+    }
+}
+
+/// How a bone is attached to its parent
+#[derive(Clone, Copy, Debug)]
+pub enum RagdollJoint {
+    /// Shoulders, hips, neck: cone swing and twist limits in radians
+    Ball { swing: f32, twist: f32 },
+    /// Elbows and knees: rotation around the local X axis
+    Hinge { min: f32, max: f32 },
+}
+
+/// One rigid body of a ragdoll, in the character's rest pose relative to its origin
+#[derive(Clone, Debug)]
+pub struct RagdollBone {
+    pub name: String,
+    pub parent: Option<usize>,
+    pub position: Vec3,
+    pub radius: f32,
+    /// Length of the capsule's cylinder part (along Y)
+    pub length: f32,
+    pub mass: f32,
+    /// Joint pivot in the rest pose, ignored for the root bone
+    pub pivot: Vec3,
+    pub joint: RagdollJoint,
+}
+
+/// Bone layout used to build a character's ragdoll
+#[derive(Component, Clone, Debug)]
+pub struct RagdollProfile {
+    pub bones: Vec<RagdollBone>,
+}
+
+impl RagdollProfile {
+    /// Eleven-bone human, origin at the pelvis
+    pub fn humanoid() -> Self {
+        use std::f32::consts::FRAC_PI_2;
+
+        let shoulder = RagdollJoint::Ball { swing: FRAC_PI_2, twist: 0.6 };
+        let hip = RagdollJoint::Ball { swing: 1.2, twist: 0.3 };
+        let elbow = RagdollJoint::Hinge { min: -2.5, max: 0.0 };
+        let knee = RagdollJoint::Hinge { min: 0.0, max: 2.5 };
+
+        let mut profile = Self { bones: Vec::new() };
+        let pelvis = profile.add(RagdollBone {
+            name: "pelvis".to_string(),
+            parent: None,
+            position: Vec3::ZERO,
+            radius: 0.15,
+            length: 0.15,
+            mass: 12.0,
+            pivot: Vec3::ZERO,
+            joint: hip,
+        });
+        let spine = profile.add(RagdollBone {
+            name: "spine".to_string(),
+            parent: Some(pelvis),
+            position: Vec3::new(0.0, 0.4, 0.0),
+            radius: 0.17,
+            length: 0.3,
+            mass: 18.0,
+            pivot: Vec3::new(0.0, 0.2, 0.0),
+            joint: RagdollJoint::Ball { swing: 0.5, twist: 0.4 },
+        });
+        profile.add(RagdollBone {
+            name: "head".to_string(),
+            parent: Some(spine),
+            position: Vec3::new(0.0, 0.85, 0.0),
+            radius: 0.12,
+            length: 0.1,
+            mass: 5.0,
+            pivot: Vec3::new(0.0, 0.7, 0.0),
+            joint: RagdollJoint::Ball { swing: 0.7, twist: 0.8 },
+        });
+
+        for (side, suffix) in [(-1.0, "l"), (1.0, "r")] {
+            let upper_arm = profile.add(RagdollBone {
+                name: format!("upper_arm_{suffix}"),
+                parent: Some(spine),
+                position: Vec3::new(0.3 * side, 0.45, 0.0),
+                radius: 0.06,
+                length: 0.25,
+                mass: 3.0,
+                pivot: Vec3::new(0.27 * side, 0.65, 0.0),
+                joint: shoulder,
+            });
+            profile.add(RagdollBone {
+                name: format!("forearm_{suffix}"),
+                parent: Some(upper_arm),
+                position: Vec3::new(0.3 * side, 0.05, 0.0),
+                radius: 0.05,
+                length: 0.25,
+                mass: 2.0,
+                pivot: Vec3::new(0.3 * side, 0.25, 0.0),
+                joint: elbow,
+            });
+
+            let thigh = profile.add(RagdollBone {
+                name: format!("thigh_{suffix}"),
+                parent: Some(pelvis),
+                position: Vec3::new(0.12 * side, -0.35, 0.0),
+                radius: 0.08,
+                length: 0.3,
+                mass: 9.0,
+                pivot: Vec3::new(0.12 * side, -0.1, 0.0),
+                joint: hip,
+            });
+            profile.add(RagdollBone {
+                name: format!("calf_{suffix}"),
+                parent: Some(thigh),
+                position: Vec3::new(0.12 * side, -0.85, 0.0),
+                radius: 0.07,
+                length: 0.3,
+                mass: 5.0,
+                pivot: Vec3::new(0.12 * side, -0.6, 0.0),
+                joint: knee,
+            });
+        }
+
+        profile
+    }
+
+    /// Appends a bone and returns its index for use as `parent`
+    pub fn add(&mut self, bone: RagdollBone) -> usize {
+        self.bones.push(bone);
+        self.bones.len() - 1
+    }
+}
+
+/// A spawned ragdoll: bone bodies and the joints holding them together
+#[derive(Component)]
+pub struct Ragdoll {
+    pub bones: Vec<Entity>,
+    pub joints: Vec<Entity>,
+    pub material: Handle<StandardMaterial>,
+    pub age: f32,
+}
+
+fn spawn_ragdolls(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    settings: Res<RagdollSettings>,
+    mut death_events: EventReader<Death>,
+    mut characters: Query<(&mut Transform, &RagdollProfile, Option<&LinearVelocity>), With<CharacterModel>>,
+) {
+    for death in death_events.read() {
+        let Ok((mut transform, profile, velocity)) = characters.get_mut(death.entity) else {
+            continue;
+        };
+
+        if !settings.enabled {
+            // Ohne Ragdoll bleibt das Modell liegen (Platzhalter für den "dying"-Clip)
+            transform.rotation *= Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2);
+            continue;
+        }
+
+        let root = *transform;
+        let velocity = velocity.map_or(Vec3::ZERO, |velocity| velocity.0);
+        let material = materials.add(StandardMaterial {
+            base_color: Color::srgb(0.8, 0.6, 0.4),
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        });
+
+        // Tödlicher Treffer stößt den nächstgelegenen Knochen an
+        let hit_bone = profile.bones
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                let distance_a = root.transform_point(a.position).distance_squared(death.point);
+                let distance_b = root.transform_point(b.position).distance_squared(death.point);
+                distance_a.total_cmp(&distance_b)
+            })
+            .map(|(index, _)| index);
+
+        let mut bones = Vec::with_capacity(profile.bones.len());
+        for (index, bone) in profile.bones.iter().enumerate() {
+            // Jeder Knochen hat einen Impuls, spätere Treffer addieren sich darauf
+            let impulse = if hit_bone == Some(index) {
+                death.force * settings.impulse_scale
+            } else {
+                Vec3::ZERO
+            };
+            let bone_entity = commands.spawn((
+                Mesh3d(meshes.add(Capsule3d::new(bone.radius, bone.length))),
+                MeshMaterial3d(material.clone()),
+                Transform::from_translation(root.transform_point(bone.position)).with_rotation(root.rotation),
+                RigidBody::Dynamic,
+                Collider::capsule(bone.radius, bone.length),
+                Mass(bone.mass),
+                // Knochen kollidieren nur mit der Welt, nicht miteinander
+                GameLayer::Debris.layers(),
+                LinearVelocity(velocity),
+                ExternalImpulse::new(impulse),
+                Name::new(format!("Ragdoll {}", bone.name)),
+            )).id();
+            bones.push(bone_entity);
+        }
+
+        let mut joints = Vec::new();
+        for (index, bone) in profile.bones.iter().enumerate() {
+            let Some(parent) = bone.parent else {
+                continue;
+            };
+
+            let parent_bone = &profile.bones[parent];
+            let anchor_1 = bone.pivot - parent_bone.position;
+            let anchor_2 = bone.pivot - bone.position;
+
+            let joint = match bone.joint {
+                RagdollJoint::Ball { swing, twist } => commands.spawn(
+                    SphericalJoint::new(bones[parent], bones[index])
+                        .with_local_anchor_1(anchor_1)
+                        .with_local_anchor_2(anchor_2)
+                        .with_swing_limits(-swing, swing)
+                        .with_twist_limits(-twist, twist),
+                ).id(),
+                RagdollJoint::Hinge { min, max } => commands.spawn(
+                    RevoluteJoint::new(bones[parent], bones[index])
+                        .with_local_anchor_1(anchor_1)
+                        .with_local_anchor_2(anchor_2)
+                        .with_aligned_axis(Vec3::X)
+                        .with_angle_limits(min, max),
+                ).id(),
+            };
+            joints.push(joint);
+        }
+
+        commands.spawn((
+            Ragdoll {
+                bones,
+                joints,
+                material,
+                age: 0.0,
+            },
+            Name::new("Ragdoll"),
+        ));
+        commands.entity(death.entity).despawn();
+
+        info!("Spawned ragdoll with {} bones", profile.bones.len());
+    }
+}
+
+fn fade_ragdolls(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<RagdollSettings>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut ragdolls: Query<(Entity, &mut Ragdoll)>,
+) {
+    // Älteste Ragdolls zuerst ausblenden, wenn es zu viele sind
+    let mut by_age: Vec<(Entity, f32)> = ragdolls.iter().map(|(entity, ragdoll)| (entity, ragdoll.age)).collect();
+    by_age.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let over_limit: Vec<Entity> = by_age
+        .iter()
+        .take(by_age.len().saturating_sub(settings.max_ragdolls))
+        .map(|(entity, _)| *entity)
+        .collect();
+
+    for (entity, mut ragdoll) in ragdolls.iter_mut() {
+        ragdoll.age += time.delta_secs();
+        if over_limit.contains(&entity) {
+            ragdoll.age = ragdoll.age.max(settings.fade_after);
+        }
+
+        let fade = (ragdoll.age - settings.fade_after) / settings.fade_duration.max(f32::EPSILON);
+        if fade <= 0.0 {
+            continue;
+        }
+
+        if fade >= 1.0 {
+            for &part in ragdoll.joints.iter().chain(ragdoll.bones.iter()) {
+                commands.entity(part).despawn();
+            }
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        if let Some(material) = materials.get_mut(&ragdoll.material) {
+            material.base_color.set_alpha(1.0 - fade);
+        }
+    }
+}
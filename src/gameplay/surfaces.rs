@@ -0,0 +1,280 @@
+//! Surface materials
+//!
+//! Maps map textures to surface properties the way GoldSrc does with
+//! `sound/materials.txt`: every line is a material type character followed by
+//! a texture name. Colliders with a [`SurfaceTexture`] get their
+//! [`SurfaceProperties`] (and friction) filled in automatically. Footsteps,
+//! bullet impacts and penetration all read from those properties. The table
+//! is an asset, so it hot reloads with the `dev` feature like weapon definitions.
+
+use std::collections::HashMap;
+
+use bevy::asset::{io::Reader, AssetLoader, LoadContext};
+use bevy::prelude::*;
+use avian3d::prelude::*;
+
+use crate::gameplay::audio::ImpactType;
+
+pub struct SurfacePlugin;
+
+impl Plugin for SurfacePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_asset::<SurfaceTable>()
+            .init_asset_loader::<SurfaceTableLoader>()
+            .init_resource::<SurfaceTableHandle>()
+            .add_event::<SurfaceImpact>()
+            .add_systems(Startup, load_surface_table)
+            .add_systems(Update, assign_surface_properties);
+    }
+}
+
+/// Asset-Pfad der Material-Tabelle (GoldSrc: sound/materials.txt)
+pub const MATERIALS_FILE: &str = "materials.txt";
+
+/// Longest texture name GoldSrc compares (CBTEXTURENAMEMAX - 1)
+const TEXTURE_NAME_MAX: usize = 12;
+
+/// GoldSrc material types
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum SurfaceMaterial {
+    #[default]
+    Concrete,
+    Metal,
+    Dirt,
+    Vent,
+    Grate,
+    Tile,
+    Slosh,
+    Wood,
+    Computer,
+    Glass,
+    Flesh,
+}
+
+impl SurfaceMaterial {
+    /// Parses the type character used in materials.txt
+    pub fn from_char(c: char) -> Option<Self> {
+        match c.to_ascii_uppercase() {
+            'C' => Some(Self::Concrete),
+            'M' => Some(Self::Metal),
+            'D' => Some(Self::Dirt),
+            'V' => Some(Self::Vent),
+            'G' => Some(Self::Grate),
+            'T' => Some(Self::Tile),
+            'S' => Some(Self::Slosh),
+            'W' => Some(Self::Wood),
+            'P' => Some(Self::Computer),
+            'Y' => Some(Self::Glass),
+            'F' => Some(Self::Flesh),
+            _ => None,
+        }
+    }
+
+    pub fn impact_type(self) -> ImpactType {
+        match self {
+            Self::Concrete | Self::Tile => ImpactType::Concrete,
+            Self::Metal | Self::Vent | Self::Grate | Self::Computer => ImpactType::Metal,
+            Self::Wood => ImpactType::Wood,
+            Self::Glass => ImpactType::Glass,
+            Self::Dirt | Self::Slosh | Self::Flesh => ImpactType::Soft,
+        }
+    }
+
+    pub fn properties(self) -> SurfaceProperties {
+        // (Reibung, Dichte für Durchschuss pro Meter)
+        let (friction, density) = match self {
+            Self::Concrete => (0.6, 2.0),
+            Self::Metal => (0.4, 3.0),
+            Self::Dirt => (0.7, 1.5),
+            Self::Vent => (0.4, 1.0),
+            Self::Grate => (0.5, 0.5),
+            Self::Tile => (0.5, 1.8),
+            Self::Slosh => (0.2, 0.5),
+            Self::Wood => (0.55, 0.6),
+            Self::Computer => (0.4, 1.2),
+            Self::Glass => (0.25, 0.3),
+            Self::Flesh => (0.6, 0.8),
+        };
+
+        SurfaceProperties {
+            material: self,
+            impact_type: self.impact_type(),
+            friction,
+            density,
+        }
+    }
+
+    /// Name used for footstep sound files. Only carpet, concrete, grass, snow
+    /// and wood exist in `assets/sounds/impact`, everything else sounds like
+    /// the closest of those
+    pub fn footstep_name(self) -> &'static str {
+        match self {
+            Self::Wood | Self::Grate => "wood",
+            Self::Dirt => "grass",
+            Self::Slosh => "snow",
+            Self::Flesh => "carpet",
+            Self::Concrete | Self::Metal | Self::Vent | Self::Tile | Self::Computer | Self::Glass => "concrete",
+        }
+    }
+
+    pub fn footstep_sound(self, step: u32) -> String {
+        format!("sounds/impact/footstep_{}_{:03}.ogg", self.footstep_name(), step % 5)
+    }
+}
+
+/// Physical and audiovisual properties of a collider's surface
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+pub struct SurfaceProperties {
+    pub material: SurfaceMaterial,
+    pub impact_type: ImpactType,
+    pub friction: f32,
+    /// Penetration cost per meter of material
+    pub density: f32,
+}
+
+impl Default for SurfaceProperties {
+    fn default() -> Self {
+        SurfaceMaterial::default().properties()
+    }
+}
+
+/// Texture name of a map collider, resolved through the [`SurfaceTable`]
+#[derive(Component, Clone, Debug)]
+pub struct SurfaceTexture(pub String);
+
+/// Something hit a surface (bullet, melee swing, ...)
+#[derive(Event, Clone, Copy, Debug)]
+pub struct SurfaceImpact {
+    pub entity: Entity,
+    pub point: Vec3,
+    pub normal: Vec3,
+    pub surface: SurfaceProperties,
+}
+
+/// Texture name -> material table, loaded from [`MATERIALS_FILE`]
+#[derive(Asset, TypePath, Debug, Default)]
+pub struct SurfaceTable {
+    pub materials: HashMap<String, SurfaceMaterial>,
+}
+
+impl SurfaceTable {
+    /// Parses materials.txt: `<type char> <texture name>` per line, `//` comments
+    pub fn parse(source: &str) -> Self {
+        let mut materials = HashMap::new();
+
+        for line in source.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            let (Some(kind), Some(name)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            let Some(material) = kind.chars().next().and_then(SurfaceMaterial::from_char) else {
+                warn!("materials.txt: unknown material type '{}'", kind);
+                continue;
+            };
+
+            materials.insert(normalize_texture_name(name), material);
+        }
+
+        Self { materials }
+    }
+
+    /// Material for a texture, concrete if the texture isn't listed
+    pub fn lookup(&self, texture: &str) -> SurfaceMaterial {
+        self.materials
+            .get(&normalize_texture_name(texture))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Strips animation/tiling prefixes (`+0`, `-1`, `{`, `!`) and truncates like GoldSrc
+fn normalize_texture_name(name: &str) -> String {
+    let mut name = name.trim();
+    if name.starts_with('+') || name.starts_with('-') {
+        name = name.get(2..).unwrap_or("");
+    }
+    let name = name.trim_start_matches(['{', '!', '~']);
+
+    name.chars().take(TEXTURE_NAME_MAX).collect::<String>().to_uppercase()
+}
+
+#[derive(Default)]
+pub struct SurfaceTableLoader;
+
+impl AssetLoader for SurfaceTableLoader {
+    type Asset = SurfaceTable;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<SurfaceTable, std::io::Error> {
+        let mut source = String::new();
+        reader.read_to_string(&mut source).await?;
+        Ok(SurfaceTable::parse(&source))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["txt"]
+    }
+}
+
+/// The loaded [`SurfaceTable`]
+#[derive(Resource, Default)]
+pub struct SurfaceTableHandle(pub Handle<SurfaceTable>);
+
+fn load_surface_table(
+    asset_server: Res<AssetServer>,
+    mut table: ResMut<SurfaceTableHandle>,
+) {
+    table.0 = asset_server.load(MATERIALS_FILE);
+}
+
+fn assign_surface_properties(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    handle: Res<SurfaceTableHandle>,
+    tables: Res<Assets<SurfaceTable>>,
+    mut table_events: EventReader<AssetEvent<SurfaceTable>>,
+    pending: Query<(Entity, &SurfaceTexture), Without<SurfaceProperties>>,
+    assigned: Query<(Entity, &SurfaceTexture), With<SurfaceProperties>>,
+    mut fallback: Local<Option<SurfaceTable>>,
+) {
+    let reloaded = table_events
+        .read()
+        .any(|event| event.is_loaded_with_dependencies(&handle.0) || event.is_modified(&handle.0));
+
+    // Bis die Tabelle da ist warten, ohne Datei ist alles Beton
+    let table: &SurfaceTable = match tables.get(&handle.0) {
+        Some(table) => {
+            if reloaded {
+                info!("Loaded {} surface materials from {}", table.materials.len(), MATERIALS_FILE);
+            }
+            table
+        }
+        None if asset_server.load_state(&handle.0).is_failed() => fallback.get_or_insert_with(|| {
+            warn!("Could not load {} - all surfaces default to concrete", MATERIALS_FILE);
+            SurfaceTable::default()
+        }),
+        None => return,
+    };
+
+    // Nach einem Hot-Reload auch schon zugewiesene Flächen neu auflösen
+    let query = pending.iter().chain(assigned.iter().filter(|_| reloaded));
+    for (entity, texture) in query {
+        let properties = table.lookup(&texture.0).properties();
+        commands.entity(entity).insert((
+            properties,
+            Friction::new(properties.friction),
+        ));
+    }
+}
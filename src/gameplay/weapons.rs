@@ -1,24 +1,32 @@
-use std::{collections::VecDeque, time::Duration};
+use std::{collections::{HashMap, VecDeque}, time::Duration};
 
+use bevy::input::mouse::MouseWheel;
 use bevy::prelude::*;
 use avian3d::prelude::*;
+use serde::Deserialize;
+
+use crate::content::weapon_definitions::{FireMode, ReloadMode, WeaponAnimationNames, WeaponDefinition, WeaponDefinitions};
+use crate::core::fps_controller::{CmdButtons, PlayerCmd};
+use crate::gameplay::damage::{self, DamageEvent, DamageType, HitGroup};
+use crate::gameplay::projectiles::SpawnProjectile;
+use crate::gameplay::surfaces::{SurfaceImpact, SurfaceProperties};
 
 pub struct WeaponPlugin;
 
 impl Plugin for WeaponPlugin {
     fn build(&self, app: &mut App) {
         app
-            .init_resource::<WeaponAnimSet>()
             .add_event::<WeaponAnimationChanged>()
             .add_systems(Startup, setup_weapon_system)
             .add_systems(Update, (
-                weapon_pickup_system,
-                weapon_usage_system,
-                //update_weapon_sway,
+                build_weapon_pickups,
+                weapon_pickup_system.after(crate::gameplay::interaction::resolve_use),
+                (weapon_select_input, update_weapon_switch).chain(),
+                weapon_fire_mode_input,
+                (update_aim_down_sights, update_accuracy, weapon_reload_system, weapon_usage_system, weapon_melee_system, recover_view_punch).chain(),
                 spawn_held_weapon_view,
                 on_scene_ready_mark_player,
                 start_idle_when_ready,
-                build_anim_graph,
                 update_weapon_animation_state,
                 handle_weapon_animation_transitions,
             ));
@@ -27,43 +35,249 @@ impl Plugin for WeaponPlugin {
 
 #[derive(Component, Reflect)]
 pub struct Weapon {
-    pub name: String,
-    pub damage: f32,
-    pub fire_rate: f32,
-    pub ammo: u32,
-    pub max_ammo: u32,
-    pub range: f32,
+    /// Id of the weapon definition (file name in `assets/weapons`)
+    pub id: String,
+    pub definition: Handle<WeaponDefinition>,
+    /// Rounds in the magazine, reloads take from the player's ammo pool
+    pub magazine: u32,
     pub last_shot: f32,
+    /// Index into the definition's `fire_modes`
+    pub fire_mode: usize,
+    /// Shots of the current burst still to fire
+    pub burst_remaining: u32,
+    /// Semi and burst need the button released before the next click
+    pub trigger_released: bool,
+}
+
+/// Ammo pool shared by all weapons firing the same rounds
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum AmmoType {
+    NineMm,
+    FiveFiveSix,
+    Buckshot,
+    Bolt,
+    Rocket,
+    Grenade,
+}
+
+impl AmmoType {
+    pub const ALL: [AmmoType; 6] = [
+        AmmoType::NineMm,
+        AmmoType::FiveFiveSix,
+        AmmoType::Buckshot,
+        AmmoType::Bolt,
+        AmmoType::Rocket,
+        AmmoType::Grenade,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::NineMm => "9mm",
+            Self::FiveFiveSix => "5.56",
+            Self::Buckshot => "buckshot",
+            Self::Bolt => "bolts",
+            Self::Rocket => "rockets",
+            Self::Grenade => "grenades",
+        }
+    }
+
+    /// Most rounds a player can carry besides the loaded magazines
+    pub fn max_carry(self) -> u32 {
+        match self {
+            Self::NineMm => 150,
+            Self::FiveFiveSix => 90,
+            Self::Buckshot => 32,
+            Self::Bolt => 20,
+            Self::Rocket => 5,
+            Self::Grenade => 10,
+        }
+    }
+
+    /// Ammo entity classnames (`ammo_9mmclip`, `item_ammo_buckshot`, ...) and their round count
+    pub fn from_classname(classname: &str) -> Option<(Self, u32)> {
+        let name = classname.to_lowercase();
+        let name = name.strip_prefix("item_").unwrap_or(&name);
+        let name = name.strip_prefix("ammo_").unwrap_or(name);
+
+        match name {
+            "9mmclip" | "glockclip" => Some((Self::NineMm, 17)),
+            "9mmar" | "mp5clip" | "arclip" => Some((Self::NineMm, 50)),
+            "9mmbox" => Some((Self::NineMm, 150)),
+            "556" | "556clip" | "556nato" => Some((Self::FiveFiveSix, 30)),
+            "556box" | "556natobox" => Some((Self::FiveFiveSix, 90)),
+            "buckshot" => Some((Self::Buckshot, 12)),
+            "crossbow" | "bolts" => Some((Self::Bolt, 5)),
+            "rpgclip" | "rockets" => Some((Self::Rocket, 1)),
+            "argrenades" | "grenades" => Some((Self::Grenade, 2)),
+            _ => None,
+        }
+    }
+}
+
+/// Reload in progress, on the weapon entity
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+pub struct Reloading {
+    pub kind: ReloadKind,
+    /// Elapsed time at which the magazine is refilled (or the next shell goes in)
+    pub finish_at: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum ReloadKind {
+    /// Empty magazine, plays the long reload
+    Full,
+    /// Rounds left in the magazine, plays the fast reload
+    Tactical,
+    /// One shell at a time, interrupted by firing
+    Shell,
 }
 
 #[derive(Component)]
 pub struct WeaponPickup {
-    pub weapon_type: WeaponType,
+    pub weapon_id: String,
     pub ammo_count: u32,
 }
 
 #[derive(Component)]
 pub struct PlayerInventory {
     pub held_weapon: Option<Entity>,
+    /// Previously held weapon, for the quick switch
+    pub last_weapon: Option<Entity>,
     pub weapons: Vec<Entity>,
-}
-
-#[derive(Clone, Debug, Copy)]
-pub enum WeaponType {
-    Pistol,
-    Rifle,
-    Shotgun,
+    /// Carried rounds per ammo type, outside the magazines
+    pub ammo: HashMap<AmmoType, u32>,
 }
 
 impl Default for PlayerInventory {
     fn default() -> Self {
         Self {
             held_weapon: None,
+            last_weapon: None,
             weapons: Vec::new(),
+            ammo: HashMap::new(),
         }
     }
 }
 
+impl PlayerInventory {
+    pub fn ammo(&self, ammo_type: AmmoType) -> u32 {
+        self.ammo.get(&ammo_type).copied().unwrap_or(0)
+    }
+
+    /// Adds rounds up to the carry limit and returns how many were taken
+    pub fn give_ammo(&mut self, ammo_type: AmmoType, amount: u32) -> u32 {
+        let carried = self.ammo.entry(ammo_type).or_insert(0);
+        let gained = amount.min(ammo_type.max_carry().saturating_sub(*carried));
+        *carried += gained;
+        gained
+    }
+
+    /// Removes up to `amount` rounds and returns how many there were
+    pub fn take_ammo(&mut self, ammo_type: AmmoType, amount: u32) -> u32 {
+        let carried = self.ammo.entry(ammo_type).or_insert(0);
+        let taken = amount.min(*carried);
+        *carried -= taken;
+        taken
+    }
+}
+
+/// Current accuracy of the player's held weapon
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+pub struct Accuracy {
+    /// Cone half angle in degrees for the next shot
+    pub spread: f32,
+    /// Extra spread from sustained fire, shrinks over time
+    pub bloom: f32,
+    /// Shots in the current burst, index into the recoil pattern
+    pub shots_fired: u32,
+}
+
+/// View kick from recoil in radians (x = pitch up, y = yaw left). Shots
+/// follow the punched view; it drifts back to zero over time.
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+pub struct ViewPunch {
+    pub angles: Vec2,
+    /// Radians per second, set by the weapon that kicked last
+    pub recovery: f32,
+}
+
+impl ViewPunch {
+    pub fn rotation(&self) -> Quat {
+        Quat::from_euler(EulerRot::YXZ, self.angles.y, self.angles.x, 0.0)
+    }
+}
+
+/// Aim-down-sights state of a player
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+pub struct AimDownSights {
+    /// 0.0 = hip, 1.0 = fully aimed
+    pub amount: f32,
+    /// Fully zoomed through a scope, the HUD draws the overlay
+    pub scoped: bool,
+}
+
+/// Deterministic 0..1 value from a command tick, so replaying the same
+/// commands reproduces every spread and recoil roll (HL `SharedRandom`)
+pub fn shared_random(tick: u32, salt: u32) -> f32 {
+    let mut x = tick.wrapping_mul(0x9E37_79B9) ^ salt.wrapping_mul(0x85EB_CA6B);
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7FEB_352D);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846C_A68B);
+    x ^= x >> 16;
+    x as f32 / u32::MAX as f32
+}
+
+/// One trigger pull: where it starts, where it aims and how far pellets scatter
+#[derive(Debug, Clone, Copy)]
+pub struct Shot {
+    pub origin: Vec3,
+    /// View rotation including punch
+    pub aim: Quat,
+    /// Cone half angle in radians
+    pub spread: f32,
+    /// Seed for [`shared_random`]
+    pub tick: u32,
+    /// Shot number in the current burst
+    pub index: u32,
+}
+
+impl Shot {
+    /// Direction of one pellet, uniformly distributed in the spread cone
+    pub fn pellet_direction(&self, pellet: u32) -> Dir3 {
+        let salt = self.index.wrapping_mul(64).wrapping_add(pellet.wrapping_mul(2));
+        let radius = self.spread * shared_random(self.tick, salt).sqrt();
+        let angle = shared_random(self.tick, salt.wrapping_add(1)) * std::f32::consts::TAU;
+        let offset = Vec2::from_angle(angle) * radius;
+        self.aim * Quat::from_euler(EulerRot::YXZ, offset.x, offset.y, 0.0) * Dir3::NEG_Z
+    }
+}
+
+/// Holster of the current weapon and draw of the next one; the player can't
+/// fire or reload until it's done
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+pub struct WeaponSwitch {
+    pub target: Entity,
+    pub phase: SwitchPhase,
+    pub started_at: f32,
+    pub duration: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum SwitchPhase {
+    Holster,
+    Draw,
+}
+
+/// Number keys for the weapon buckets 1-5
+const WEAPON_SLOT_KEYS: [KeyCode; 5] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+];
+
 
 #[derive(Component)]
 struct WeaponAnimRoot;      // Marker am Root der View-Model-Scene
@@ -103,224 +317,655 @@ impl Default for WeaponAnimationState {
 
 
 
-fn setup_weapon_system(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    game_assets: Res<crate::content::assets::GameAssets>,
-) {
+fn setup_weapon_system(mut commands: Commands) {
     // Create some weapon pickups in the world
-    spawn_weapon_pickup(
-        &mut commands,
-        &mut meshes,
-        &mut materials,
-        &game_assets,
-        WeaponType::Pistol,
-        Vec3::new(-5.0, 1.0, -5.0),
-        30,
-    );
-
-    spawn_weapon_pickup(
-        &mut commands,
-        &mut meshes,
-        &mut materials,
-        &game_assets,
-        WeaponType::Rifle,
-        Vec3::new(5.0, 1.0, -5.0),
-        90,
-    );
-
-    spawn_weapon_pickup(
-        &mut commands,
-        &mut meshes,
-        &mut materials,
-        &game_assets,
-        WeaponType::Shotgun,
-        Vec3::new(0.0, 1.0, -8.0),
-        12,
-    );
-}
-
-fn spawn_weapon_pickup(
+    spawn_weapon_pickup(&mut commands, "crowbar", Vec3::new(-5.0, 1.0, -8.0), 0);
+    spawn_weapon_pickup(&mut commands, "pistol", Vec3::new(-5.0, 1.0, -5.0), 30);
+    spawn_weapon_pickup(&mut commands, "rifle", Vec3::new(5.0, 1.0, -5.0), 90);
+    spawn_weapon_pickup(&mut commands, "shotgun", Vec3::new(0.0, 1.0, -8.0), 12);
+    spawn_weapon_pickup(&mut commands, "crossbow", Vec3::new(-3.0, 1.0, -10.0), 5);
+    spawn_weapon_pickup(&mut commands, "rpg", Vec3::new(3.0, 1.0, -10.0), 2);
+    spawn_weapon_pickup(&mut commands, "grenade_launcher", Vec3::new(0.0, 1.0, -12.0), 4);
+}
+
+/// Spawns a pickup for a weapon definition id. Mesh and collider are added by
+/// [`build_weapon_pickups`] once the definition is loaded.
+pub(crate) fn spawn_weapon_pickup(
     commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<StandardMaterial>>,
-    game_assets: &crate::content::assets::GameAssets,
-    weapon_type: WeaponType,
+    weapon_id: &str,
     position: Vec3,
     ammo: u32,
 ) {
-    let _pickup_entity = if game_assets.assets_loaded {
-        // Use actual weapon model for rifle (since we have the saiga model)
-        match weapon_type {
-            /*WeaponType::Rifle => {
-                commands.spawn((
-                    SceneRoot(game_assets.weapon_model.clone()),
-                    Transform::from_translation(position).with_scale(Vec3::splat(0.3)),
-                    RigidBody::Static,
-                    Collider::cuboid(0.2, 0.2, 1.0), // Approximate collider
-                    WeaponPickup {
-                        weapon_type,
-                        ammo_count: ammo,
-                    },
-                    crate::content::assets::WeaponModel,
-                )).id()
-            },*/
-            _ => {
-                // For other weapons, use placeholder shapes
-                let (color, size) = match weapon_type {
-                    WeaponType::Pistol => (Color::srgb(0.7, 0.7, 0.9), Vec3::new(0.3, 0.2, 0.6)),
-                    WeaponType::Shotgun => (Color::srgb(0.6, 0.4, 0.2), Vec3::new(0.25, 0.25, 1.0)),
-                    _ => (Color::srgb(0.5, 0.5, 0.5), Vec3::new(0.2, 0.2, 0.8)),
-                };
+    commands.spawn((
+        Transform::from_translation(position),
+        Visibility::default(),
+        RigidBody::Static,
+        Sensor,
+        crate::core::physics::GameLayer::Pickup.layers(),
+        WeaponPickup {
+            weapon_id: weapon_id.to_string(),
+            ammo_count: ammo,
+        },
+        Name::new(format!("WeaponPickup - {}", weapon_id)),
+    ));
 
-                commands.spawn((
-                    Mesh3d(meshes.add(Cuboid::from_size(size))),
-                    MeshMaterial3d(materials.add(StandardMaterial {
-                        base_color: color,
-                        metallic: 0.8,
-                        perceptual_roughness: 0.2,
-                        ..default()
-                    })),
-                    Transform::from_translation(position),
-                    RigidBody::Static,
-                    Collider::cuboid(size.x, size.y, size.z),
-                    WeaponPickup {
-                        weapon_type,
-                        ammo_count: ammo,
-                    },
-                )).id()
-            }
+    info!("Spawned {} weapon pickup at {:?}", weapon_id, position);
+}
+
+/// Gives pickups the placeholder shape from their definition, again after a hot reload
+fn build_weapon_pickups(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut definition_events: EventReader<AssetEvent<WeaponDefinition>>,
+    definitions: Res<WeaponDefinitions>,
+    weapon_assets: Res<Assets<WeaponDefinition>>,
+    pickup_query: Query<(Entity, &WeaponPickup, Has<Mesh3d>)>,
+) {
+    let modified: Vec<AssetId<WeaponDefinition>> = definition_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for (entity, pickup, has_mesh) in pickup_query.iter() {
+        let Some(handle) = definitions.get(&pickup.weapon_id) else {
+            continue;
+        };
+        if has_mesh && !modified.contains(&handle.id()) {
+            continue;
         }
-    } else {
-        // Fallback to placeholder shapes if assets not loaded
-        let (color, size) = match weapon_type {
-            WeaponType::Pistol => (Color::srgb(0.7, 0.7, 0.9), Vec3::new(0.3, 0.2, 0.6)),
-            WeaponType::Rifle => (Color::srgb(0.3, 0.3, 0.3), Vec3::new(0.2, 0.2, 1.2)),
-            WeaponType::Shotgun => (Color::srgb(0.6, 0.4, 0.2), Vec3::new(0.25, 0.25, 1.0)),
+        let Some(definition) = weapon_assets.get(handle) else {
+            continue;
         };
 
-        commands.spawn((
+        let size = Vec3::from(definition.pickup.size);
+        let [red, green, blue] = definition.pickup.color;
+        commands.entity(entity).insert((
             Mesh3d(meshes.add(Cuboid::from_size(size))),
             MeshMaterial3d(materials.add(StandardMaterial {
-                base_color: color,
+                base_color: Color::srgb(red, green, blue),
                 metallic: 0.8,
                 perceptual_roughness: 0.2,
                 ..default()
             })),
-            Transform::from_translation(position),
-            RigidBody::Static,
             Collider::cuboid(size.x, size.y, size.z),
-            WeaponPickup {
-                weapon_type,
-                ammo_count: ammo,
-            },
-        )).id()
-    };
-
-    info!("Spawned {:?} weapon pickup at {:?}", weapon_type, position);
+        ));
+    }
 }
 
 fn weapon_pickup_system(
     mut commands: Commands,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut player_query: Query<(&Transform, &mut PlayerInventory), With<crate::core::fps_controller::FpsController>>,
-    pickup_query: Query<(Entity, &Transform, &WeaponPickup), Without<crate::core::fps_controller::FpsController>>,
+    mut use_events: EventReader<crate::gameplay::interaction::PlayerUse>,
+    definitions: Res<WeaponDefinitions>,
+    weapon_assets: Res<Assets<WeaponDefinition>>,
+    mut player_query: Query<&mut PlayerInventory, With<crate::core::fps_controller::FpsController>>,
+    pickup_query: Query<&WeaponPickup>,
+    weapon_query: Query<&Weapon>,
+) {
+    for event in use_events.read() {
+        let crate::gameplay::interaction::UseTarget::Item(pickup_entity) = event.target else {
+            continue;
+        };
+        let Ok(weapon_pickup) = pickup_query.get(pickup_entity) else {
+            continue;
+        };
+        let Ok(mut inventory) = player_query.get_mut(event.player) else {
+            continue;
+        };
+
+        // Create weapon entity
+        let Some(weapon) = create_weapon(&definitions, &weapon_assets, &weapon_pickup.weapon_id, weapon_pickup.ammo_count) else {
+            warn!("Unknown weapon '{}'", weapon_pickup.weapon_id);
+            continue;
+        };
+        let Some(definition) = weapon_assets.get(&weapon.definition) else {
+            continue;
+        };
+
+        let owned = inventory.weapons
+            .iter()
+            .any(|&entity| weapon_query.get(entity).is_ok_and(|owned| owned.id == weapon.id));
+        if owned {
+            // Schon vorhanden: nur die Munition mitnehmen
+            let Some(ammo_type) = definition.ammo_type else {
+                continue;
+            };
+            let gained = inventory.give_ammo(ammo_type, weapon_pickup.ammo_count);
+            if gained == 0 {
+                continue;
+            }
+            info!("Took {} {} rounds from {}", gained, ammo_type.name(), definition.name);
+        } else {
+            // Was nicht ins Magazin passt, geht in den Munitionsvorrat
+            if let Some(ammo_type) = definition.ammo_type {
+                inventory.give_ammo(ammo_type, weapon_pickup.ammo_count - weapon.magazine);
+                info!("Picked up {} with {} / {} ammo", definition.name, weapon.magazine, inventory.ammo(ammo_type));
+            } else {
+                info!("Picked up {}", definition.name);
+            }
+            let weapon_entity = commands.spawn(weapon).id();
+
+            // Add to inventory
+            inventory.weapons.push(weapon_entity);
+            if inventory.held_weapon.is_none() {
+                inventory.held_weapon = Some(weapon_entity);
+            }
+        }
+
+        // Remove pickup
+        commands.entity(pickup_entity).despawn();
+    }
+}
+
+/// Builds a weapon from its definition id with up to a full magazine of
+/// `ammo`, `None` if the id isn't loaded
+pub fn create_weapon(
+    definitions: &WeaponDefinitions,
+    weapon_assets: &Assets<WeaponDefinition>,
+    id: &str,
+    ammo: u32,
+) -> Option<Weapon> {
+    let handle = definitions.get(id)?;
+    let definition = weapon_assets.get(handle)?;
+
+    Some(Weapon {
+        id: id.to_string(),
+        definition: handle.clone(),
+        magazine: ammo.min(definition.magazine_size),
+        last_shot: 0.0,
+        fire_mode: 0,
+        burst_remaining: 0,
+        trigger_released: true,
+    })
+}
+
+/// Number keys pick a bucket (again = next weapon in it), the mouse wheel
+/// cycles through all weapons and X switches back to the last one
+fn weapon_select_input(
+    mut commands: Commands,
     time: Res<Time>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut wheel_events: EventReader<MouseWheel>,
+    weapon_assets: Res<Assets<WeaponDefinition>>,
+    player_query: Query<(Entity, &PlayerInventory, Option<&WeaponSwitch>), With<crate::core::fps_controller::FpsController>>,
+    weapon_query: Query<&Weapon>,
 ) {
-    if !keyboard_input.just_pressed(KeyCode::KeyE) {
+    let scroll: f32 = wheel_events.read().map(|event| event.y).sum();
+    let slot = WEAPON_SLOT_KEYS
+        .iter()
+        .position(|key| keyboard_input.just_pressed(*key))
+        .map(|index| index as u8 + 1);
+    let quick_switch = keyboard_input.just_pressed(KeyCode::KeyX);
+    if slot.is_none() && scroll == 0.0 && !quick_switch {
         return;
     }
 
-    for (player_transform, mut inventory) in player_query.iter_mut() {
-        for (pickup_entity, pickup_transform, weapon_pickup) in pickup_query.iter() {
-            let distance = player_transform.translation.distance(pickup_transform.translation);
-            
-            if distance <= 2.0 { // Within pickup range
-                // Create weapon entity
-                let weapon = match weapon_pickup.weapon_type {
-                    WeaponType::Pistol => Weapon {
-                        name: "Pistol".to_string(),
-                        damage: 25.0,
-                        fire_rate: 0.3,
-                        ammo: weapon_pickup.ammo_count,
-                        max_ammo: 60,
-                        range: 50.0,
-                        last_shot: 0.0,
-                    },
-                    WeaponType::Rifle => Weapon {
-                        name: "Rifle".to_string(),
-                        damage: 45.0,
-                        fire_rate: 0.1,
-                        ammo: weapon_pickup.ammo_count,
-                        max_ammo: 120,
-                        range: 100.0,
-                        last_shot: 0.0,
-                    },
-                    WeaponType::Shotgun => Weapon {
-                        name: "Shotgun".to_string(),
-                        damage: 80.0,
-                        fire_rate: 0.8,
-                        ammo: weapon_pickup.ammo_count,
-                        max_ammo: 24,
-                        range: 15.0,
-                        last_shot: 0.0,
-                    },
-                };
+    for (player_entity, inventory, switch) in player_query.iter() {
+        // Das Ziel eines laufenden Wechsels gilt schon als aktuelle Waffe
+        let current = switch.map(|switch| switch.target).or(inventory.held_weapon);
 
-                let weapon_entity = commands.spawn(weapon).id();
-                
-                // Add to inventory
-                inventory.weapons.push(weapon_entity);
-                if inventory.held_weapon.is_none() {
-                    inventory.held_weapon = Some(weapon_entity);
+        let mut ordered: Vec<(Entity, u8, u8)> = inventory.weapons
+            .iter()
+            .filter_map(|&entity| {
+                let definition = weapon_assets.get(&weapon_query.get(entity).ok()?.definition)?;
+                Some((entity, definition.slot, definition.slot_position))
+            })
+            .collect();
+        ordered.sort_by_key(|&(_, slot, position)| (slot, position));
+
+        let target = if quick_switch {
+            inventory.last_weapon.filter(|weapon| inventory.weapons.contains(weapon))
+        } else if let Some(slot) = slot {
+            let bucket: Vec<Entity> = ordered
+                .iter()
+                .filter(|(_, weapon_slot, _)| *weapon_slot == slot)
+                .map(|(entity, _, _)| *entity)
+                .collect();
+            match current.and_then(|current| bucket.iter().position(|&entity| entity == current)) {
+                Some(index) => Some(bucket[(index + 1) % bucket.len()]),
+                None => bucket.first().copied(),
+            }
+        } else if ordered.is_empty() {
+            None
+        } else {
+            let count = ordered.len();
+            let next = match current.and_then(|current| ordered.iter().position(|(entity, _, _)| *entity == current)) {
+                // Mausrad hoch = vorherige Waffe wie in HL
+                Some(index) if scroll > 0.0 => (index + count - 1) % count,
+                Some(index) => (index + 1) % count,
+                None => 0,
+            };
+            Some(ordered[next].0)
+        };
+
+        let Some(target) = target else {
+            continue;
+        };
+        if Some(target) == current {
+            continue;
+        }
+
+        let current_time = time.elapsed_secs();
+        let next_switch = match (switch, inventory.held_weapon) {
+            // Noch beim Wegstecken: nur das Ziel ändern
+            (Some(switch), _) if switch.phase == SwitchPhase::Holster => WeaponSwitch { target, ..*switch },
+            (_, Some(held)) => {
+                // Wegstecken bricht ein laufendes Nachladen ab
+                commands.entity(held).remove::<Reloading>();
+                let holster_time = weapon_query.get(held).ok()
+                    .and_then(|weapon| weapon_assets.get(&weapon.definition))
+                    .map_or(0.0, |definition| definition.holster_time);
+                WeaponSwitch {
+                    target,
+                    phase: SwitchPhase::Holster,
+                    started_at: current_time,
+                    duration: holster_time,
                 }
+            }
+            (_, None) => WeaponSwitch {
+                target,
+                phase: SwitchPhase::Holster,
+                started_at: current_time,
+                duration: 0.0,
+            },
+        };
+        commands.entity(player_entity).insert(next_switch);
+    }
+}
 
-                // Remove pickup
-                commands.entity(pickup_entity).despawn();
-                
-                info!("Picked up {} with {} ammo", 
-                    match weapon_pickup.weapon_type {
-                        WeaponType::Pistol => "Pistol",
-                        WeaponType::Rifle => "Rifle", 
-                        WeaponType::Shotgun => "Shotgun",
-                    },
-                    weapon_pickup.ammo_count
-                );
-                break;
+/// Runs the holster/draw timing, swaps the held weapon in between and
+/// lowers the viewmodel accordingly
+fn update_weapon_switch(
+    mut commands: Commands,
+    time: Res<Time>,
+    weapon_assets: Res<Assets<WeaponDefinition>>,
+    weapon_query: Query<&Weapon>,
+    mut player_query: Query<(Entity, &mut PlayerInventory, &mut WeaponSwitch)>,
+    mut viewmodel_query: Query<&mut crate::rendering::view_motion::ViewmodelMotion, With<HeldWeaponView>>,
+) {
+    let current_time = time.elapsed_secs();
+
+    for (player_entity, mut inventory, mut switch) in player_query.iter_mut() {
+        let progress = ((current_time - switch.started_at) / switch.duration.max(f32::EPSILON)).clamp(0.0, 1.0);
+        let lower = match switch.phase {
+            SwitchPhase::Holster => progress,
+            SwitchPhase::Draw => 1.0 - progress,
+        };
+        for mut motion in viewmodel_query.iter_mut() {
+            motion.lower = lower;
+        }
+
+        if progress < 1.0 {
+            continue;
+        }
+
+        match switch.phase {
+            SwitchPhase::Holster => {
+                if inventory.held_weapon != Some(switch.target) {
+                    inventory.last_weapon = inventory.held_weapon;
+                    inventory.held_weapon = Some(switch.target);
+                }
+
+                let definition = weapon_query.get(switch.target).ok()
+                    .and_then(|weapon| weapon_assets.get(&weapon.definition));
+                info!("Drawing {}", definition.map_or("weapon", |definition| definition.name.as_str()));
+                switch.phase = SwitchPhase::Draw;
+                switch.started_at = current_time;
+                switch.duration = definition.map_or(0.0, |definition| definition.draw_time);
+            }
+            SwitchPhase::Draw => {
+                commands.entity(player_entity).remove::<WeaponSwitch>();
             }
         }
     }
 }
 
+/// Spread from movement, air state and sustained fire; resets the recoil
+/// pattern after a pause
+fn update_accuracy(
+    time: Res<Time>,
+    weapon_assets: Res<Assets<WeaponDefinition>>,
+    weapon_query: Query<&Weapon>,
+    mut player_query: Query<(&crate::core::fps_controller::FpsController, &LinearVelocity, &PlayerInventory, &mut Accuracy, Option<&AimDownSights>)>,
+) {
+    let current_time = time.elapsed_secs();
+
+    for (controller, velocity, inventory, mut accuracy, aim) in player_query.iter_mut() {
+        let held = inventory.held_weapon
+            .and_then(|entity| weapon_query.get(entity).ok())
+            .and_then(|weapon| Some((weapon, weapon_assets.get(&weapon.definition)?)));
+        let Some((weapon, definition)) = held else {
+            *accuracy = Accuracy::default();
+            continue;
+        };
+        let spread = &definition.spread;
+
+        accuracy.bloom = (accuracy.bloom - spread.recovery * time.delta_secs()).max(0.0);
+        if current_time - weapon.last_shot > definition.recoil.reset_time {
+            accuracy.shots_fired = 0;
+        }
+
+        let speed = Vec2::new(velocity.x, velocity.z).length() / controller.speed.max(0.1);
+        let mut current = spread.base + spread.moving * speed.min(1.0) + accuracy.bloom;
+        if !controller.is_grounded {
+            current += spread.air;
+        }
+        accuracy.spread = current.min(spread.max.max(spread.base));
+
+        // Zielen zieht den Kegel zusammen
+        if let (Some(aim), Some(aim_definition)) = (aim, &definition.aim) {
+            accuracy.spread *= 1.0 + (aim_definition.spread_scale - 1.0) * aim.amount;
+        }
+    }
+}
+
+/// B cycles through the fire modes of the held weapon
+fn weapon_fire_mode_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    weapon_assets: Res<Assets<WeaponDefinition>>,
+    player_query: Query<&PlayerInventory, With<crate::core::fps_controller::FpsController>>,
+    mut weapon_query: Query<&mut Weapon>,
+) {
+    if !keyboard.just_pressed(KeyCode::KeyB) {
+        return;
+    }
+
+    for inventory in player_query.iter() {
+        let Some(mut weapon) = inventory.held_weapon.and_then(|entity| weapon_query.get_mut(entity).ok()) else {
+            continue;
+        };
+        let Some(definition) = weapon_assets.get(&weapon.definition) else {
+            continue;
+        };
+        if definition.fire_modes.len() < 2 {
+            continue;
+        }
+
+        weapon.fire_mode = (weapon.fire_mode + 1) % definition.fire_modes.len();
+        weapon.burst_remaining = 0;
+        info!("{} fire mode: {}", definition.name, definition.fire_modes[weapon.fire_mode].name());
+    }
+}
+
+/// Right mouse button raises the sights: blends camera FOV, mouse
+/// sensitivity and viewmodel position, scopes hide the viewmodel
+fn update_aim_down_sights(
+    time: Res<Time>,
+    weapon_assets: Res<Assets<WeaponDefinition>>,
+    weapon_query: Query<(&Weapon, Has<Reloading>)>,
+    mut player_query: Query<(&PlayerCmd, &PlayerInventory, &mut AimDownSights, &mut crate::core::fps_controller::FpsController, Has<WeaponSwitch>)>,
+    mut camera_query: Query<&mut Projection, With<crate::core::fps_controller::PlayerCamera>>,
+    mut viewmodel_query: Query<(&mut crate::rendering::view_motion::ViewmodelMotion, &mut Visibility), With<HeldWeaponView>>,
+) {
+    // Standard-FOV der Kamera, solange es keine FOV-Einstellung gibt
+    let base_fov = PerspectiveProjection::default().fov;
+
+    for (cmd, inventory, mut aim, mut controller, switching) in player_query.iter_mut() {
+        let held = inventory.held_weapon
+            .and_then(|entity| weapon_query.get(entity).ok())
+            .and_then(|(weapon, reloading)| Some((weapon_assets.get(&weapon.definition)?, reloading)));
+        let aim_definition = held.and_then(|(definition, _)| definition.aim.as_ref());
+        let reloading = held.is_some_and(|(_, reloading)| reloading);
+
+        let wants_aim = aim_definition.is_some()
+            && cmd.buttons.contains(CmdButtons::ATTACK2)
+            && !switching
+            && !reloading;
+        let step = time.delta_secs() / aim_definition.map_or(0.2, |aim| aim.time).max(0.01);
+        aim.amount = if wants_aim {
+            (aim.amount + step).min(1.0)
+        } else {
+            (aim.amount - step).max(0.0)
+        };
+        aim.scoped = aim_definition.is_some_and(|aim_definition| aim_definition.scope) && aim.amount >= 0.9;
+
+        let (fov, sensitivity) = aim_definition.map_or((base_fov, 1.0), |aim_definition| {
+            (aim_definition.fov.to_radians(), aim_definition.sensitivity)
+        });
+        controller.sensitivity_scale = 1.0 + (sensitivity - 1.0) * aim.amount;
+
+        for mut projection in camera_query.iter_mut() {
+            if let Projection::Perspective(perspective) = projection.as_mut() {
+                perspective.fov = base_fov + (fov - base_fov) * aim.amount;
+            }
+        }
+
+        for (mut motion, mut visibility) in viewmodel_query.iter_mut() {
+            motion.aim = aim.amount;
+            let target = if aim.scoped { Visibility::Hidden } else { Visibility::Inherited };
+            visibility.set_if_neq(target);
+        }
+    }
+}
+
+/// Lets the view punch drift back to zero, faster the further it is out (HL `V_DropPunchAngle`)
+fn recover_view_punch(
+    time: Res<Time>,
+    mut query: Query<&mut ViewPunch>,
+) {
+    for mut punch in query.iter_mut() {
+        let length = punch.angles.length();
+        if length <= 0.0 {
+            continue;
+        }
+        let drop = (punch.recovery + length * 0.5) * time.delta_secs();
+        punch.angles *= (length - drop).max(0.0) / length;
+    }
+}
+
+/// Starts reloads (R, or firing with an empty magazine) and refills the
+/// magazine when the reload animation is over
+fn weapon_reload_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    weapon_assets: Res<Assets<WeaponDefinition>>,
+    mut player_query: Query<(&PlayerCmd, &mut PlayerInventory, Has<crate::gameplay::interaction::Carrying>, Has<crate::gameplay::interaction::AttackConsumed>), (With<crate::core::fps_controller::FpsController>, Without<WeaponSwitch>)>,
+    mut weapon_query: Query<(&mut Weapon, Option<&mut Reloading>)>,
+    view_anims: Query<&WeaponAnimSet, With<WeaponAnimRoot>>,
+    mut animation_change_event: EventWriter<WeaponAnimationChanged>,
+) {
+    let current_time = time.elapsed_secs();
+
+    for (cmd, mut inventory, carrying, attack_consumed) in player_query.iter_mut() {
+        let Some(weapon_entity) = inventory.held_weapon else {
+            continue;
+        };
+        let Ok((mut weapon, reloading)) = weapon_query.get_mut(weapon_entity) else {
+            continue;
+        };
+        let Some(definition) = weapon_assets.get(&weapon.definition) else {
+            continue;
+        };
+        let Some(ammo_type) = definition.ammo_type else {
+            continue;
+        };
+
+        if let Some(mut reloading) = reloading {
+            if current_time < reloading.finish_at {
+                continue;
+            }
+
+            let wanted = match reloading.kind {
+                ReloadKind::Full | ReloadKind::Tactical => definition.magazine_size.saturating_sub(weapon.magazine),
+                ReloadKind::Shell => 1,
+            };
+            weapon.magazine += inventory.take_ammo(ammo_type, wanted);
+            let reserve = inventory.ammo(ammo_type);
+
+            if reloading.kind == ReloadKind::Shell && weapon.magazine < definition.magazine_size && reserve > 0 {
+                // Nächste Patrone, Animation neu starten
+                reloading.finish_at += definition.reload_time;
+                animation_change_event.write(WeaponAnimationChanged);
+            } else {
+                commands.entity(weapon_entity).remove::<Reloading>();
+                animation_change_event.write(WeaponAnimationChanged);
+                info!("Reloaded {} - {} / {}", definition.name, weapon.magazine, reserve);
+            }
+            continue;
+        }
+
+        // Ein Klick zum Werfen lädt keine leere Waffe nach
+        let attacking = cmd.buttons.contains(CmdButtons::ATTACK) && !carrying && !attack_consumed;
+        let wants_reload = cmd.buttons.contains(CmdButtons::RELOAD)
+            || (attacking && weapon.magazine == 0);
+        if !wants_reload || weapon.magazine >= definition.magazine_size || inventory.ammo(ammo_type) == 0 {
+            continue;
+        }
+
+        let anims = view_anims.iter().next();
+        let (kind, duration) = match definition.reload_mode {
+            ReloadMode::Shell => (ReloadKind::Shell, definition.reload_time),
+            ReloadMode::Magazine if weapon.magazine == 0 => (
+                ReloadKind::Full,
+                anims.and_then(|anims| anims.reload_duration).unwrap_or(definition.reload_time),
+            ),
+            ReloadMode::Magazine => (
+                ReloadKind::Tactical,
+                anims.and_then(|anims| anims.reload_fast_duration).unwrap_or(definition.reload_time),
+            ),
+        };
+
+        commands.entity(weapon_entity).insert(Reloading {
+            kind,
+            finish_at: current_time + duration,
+        });
+        info!("Reloading {} ({:?})", definition.name, kind);
+    }
+}
+
 // mut anim_player_query: Query<&mut WeaponAnimationState, With<WeaponAnimPlayer>>,
 fn weapon_usage_system(
     mut commands: Commands,
-    mouse_input: Res<ButtonInput<MouseButton>>,
-    mut player_query: Query<(&Transform, &mut PlayerInventory), With<crate::core::fps_controller::FpsController>>,
-    mut weapon_query: Query<&mut Weapon>,
+    // Solange etwas getragen wird, wirft Feuer das Objekt statt zu schießen
+    mut player_query: Query<(Entity, &Transform, &PlayerCmd, &mut PlayerInventory, &mut Accuracy, &mut ViewPunch, Option<&LinearVelocity>, Option<&Children>), (With<crate::core::fps_controller::FpsController>, Without<crate::gameplay::interaction::Carrying>, Without<crate::gameplay::interaction::AttackConsumed>, Without<WeaponSwitch>)>,
+    mut weapon_query: Query<(&mut Weapon, Option<&Reloading>)>,
+    weapon_assets: Res<Assets<WeaponDefinition>>,
     time: Res<Time>,
     spatial_query: SpatialQuery,
     mut anim_player_query: Query<&mut WeaponAnimationState, With<WeaponAnimPlayer>>,
+    mut debug_rays: ResMut<crate::core::physics::DebugRays>,
+    surface_query: Query<&SurfaceProperties>,
+    mut impact_events: EventWriter<SurfaceImpact>,
+    mut damage_events: EventWriter<DamageEvent>,
+    hit_groups: Query<(&HitGroup, &ChildOf)>,
+    mut projectile_events: EventWriter<SpawnProjectile>,
 ) {
-    for (player_transform, mut inventory) in player_query.iter_mut() {
+    for (player_entity, player_transform, cmd, mut inventory, mut accuracy, mut punch, velocity, children) in player_query.iter_mut() {
         if let Some(weapon_entity) = inventory.held_weapon {
-            if let Ok(mut weapon) = weapon_query.get_mut(weapon_entity) {
+            if let Ok((mut weapon, reloading)) = weapon_query.get_mut(weapon_entity) {
+                let Some(definition) = weapon_assets.get(&weapon.definition) else {
+                    continue;
+                };
+                // Nahkampf läuft über weapon_melee_system
+                if definition.melee.is_some() {
+                    continue;
+                }
+
+                // Magazin-Reload blockiert, Schrot-Nachladen wird durch Schießen abgebrochen
+                if let Some(reloading) = reloading {
+                    if reloading.kind != ReloadKind::Shell || !cmd.buttons.contains(CmdButtons::ATTACK) || weapon.magazine == 0 {
+                        continue;
+                    }
+                    commands.entity(weapon_entity).remove::<Reloading>();
+                }
+
                 let current_time = time.elapsed_secs();
+
+                // Nicht den eigenen Körper oder die eigene Kopf-Hitbox treffen
+                let shooter = std::iter::once(player_entity)
+                    .chain(children.into_iter().flat_map(|children| children.iter()));
+                let filter = crate::core::physics::hitscan_filter().with_excluded_entities(shooter);
                 
+                let trigger = cmd.buttons.contains(CmdButtons::ATTACK);
+                if !trigger {
+                    weapon.trigger_released = true;
+                }
+                if weapon.magazine == 0 {
+                    weapon.burst_remaining = 0;
+                }
+
+                // Eine laufende Salve feuert auch nach dem Loslassen weiter
+                let mode = definition.fire_modes.get(weapon.fire_mode).copied().unwrap_or(FireMode::Auto);
+                let wants_fire = weapon.burst_remaining > 0 || match mode {
+                    FireMode::Auto => trigger,
+                    FireMode::Semi | FireMode::Burst(_) => trigger && weapon.trigger_released,
+                };
+
                 // Check if we can fire (fire rate cooldown)
-                if mouse_input.pressed(MouseButton::Left) 
-                    && current_time - weapon.last_shot >= weapon.fire_rate 
-                    && weapon.ammo > 0
+                if wants_fire
+                    && current_time - weapon.last_shot >= definition.fire_rate 
+                    && weapon.magazine > 0
                 {
-                    
+                    if weapon.burst_remaining > 0 {
+                        weapon.burst_remaining -= 1;
+                    } else {
+                        weapon.trigger_released = false;
+                        if let FireMode::Burst(count) = mode {
+                            weapon.burst_remaining = count.saturating_sub(1);
+                        }
+                    }
+
                     for mut anim_state in anim_player_query.iter_mut() {
                         anim_state.firing_time = current_time;
                         anim_state.is_firing = true;
                     }
 
+                    // Schuss folgt Blick + Punch, die Pellets streuen im Spread-Kegel
+                    let shot = accuracy.shots_fired;
+                    let aimed_shot = Shot {
+                        origin: player_transform.translation,
+                        aim: player_transform.rotation * punch.rotation(),
+                        spread: accuracy.spread.to_radians(),
+                        tick: cmd.tick,
+                        index: shot,
+                    };
+
+                    // Rückstoß nach Muster, danach der letzte Kick mit zufälliger Seite wie in CS
+                    let pattern = &definition.recoil.pattern;
+                    if let Some(&[pitch, yaw]) = pattern.get((shot as usize).min(pattern.len().saturating_sub(1))) {
+                        let side = if shot as usize >= pattern.len() && shared_random(cmd.tick, shot ^ 0x5EED) < 0.5 { -1.0 } else { 1.0 };
+                        punch.angles += Vec2::new(pitch.to_radians(), -yaw.to_radians() * side);
+                        punch.recovery = definition.recoil.recovery.to_radians();
+                    }
+                    accuracy.shots_fired += 1;
+                    accuracy.bloom += definition.spread.per_shot;
+
+                    // Projektilwaffen schießen einen Körper statt eines Strahls
+                    if let Some(projectile) = &definition.projectile {
+                        weapon.magazine -= 1;
+                        weapon.last_shot = current_time;
+                        let direction = aimed_shot.pellet_direction(0);
+                        projectile_events.write(SpawnProjectile {
+                            origin: aimed_shot.origin + direction * 0.7,
+                            direction,
+                            definition: projectile.clone(),
+                            damage: definition.damage,
+                            inherited_velocity: velocity.map_or(Vec3::ZERO, |velocity| velocity.0),
+                            owner: Some(player_entity),
+                            weapon: Some(weapon_entity),
+                        });
+                        continue;
+                    }
+
                     // Fire weapon
-                    fire_weapon(&mut weapon, player_transform, &spatial_query, current_time);
+                    fire_weapon(
+                        &mut weapon,
+                        definition,
+                        weapon_entity,
+                        player_entity,
+                        &aimed_shot,
+                        &spatial_query,
+                        &filter,
+                        &mut debug_rays,
+                        &surface_query,
+                        &mut impact_events,
+                        &mut damage_events,
+                        &hit_groups,
+                        current_time,
+                    );
                 }
             }
         }
@@ -329,70 +974,364 @@ fn weapon_usage_system(
 
 fn fire_weapon(
     weapon: &mut Weapon,
-    player_transform: &Transform,
+    definition: &WeaponDefinition,
+    weapon_entity: Entity,
+    shooter: Entity,
+    shot: &Shot,
     spatial_query: &SpatialQuery,
+    filter: &SpatialQueryFilter,
+    debug_rays: &mut crate::core::physics::DebugRays,
+    surface_query: &Query<&SurfaceProperties>,
+    impact_events: &mut EventWriter<SurfaceImpact>,
+    damage_events: &mut EventWriter<DamageEvent>,
+    hit_groups: &Query<(&HitGroup, &ChildOf)>,
     current_time: f32,
 ) {
-    weapon.ammo -= 1;
+    weapon.magazine -= 1;
     weapon.last_shot = current_time;
     
-    // Cast ray from player forward
-    let ray_start = player_transform.translation;
-    let ray_direction = -player_transform.local_z().normalize();
-    
-    if let Ok(ray_dir) = Dir3::new(ray_direction) {
-        if let Some(hit) = spatial_query.cast_ray(
-            ray_start,
-            ray_dir,
-            weapon.range,
-            true,
-            &SpatialQueryFilter::default(),
-        ) {
-            info!("Hit target at distance: {:.2} with {}", hit.distance, weapon.name);
-            // Here you could add impact effects, damage to enemies, etc.
+    // Treffer pro Ziel sammeln: ein DamageEvent pro Ziel statt eins pro Pellet
+    let mut hits: Vec<DamageEvent> = Vec::new();
+
+    for pellet in 0..definition.pellets.max(1) {
+        let ray_dir = shot.pellet_direction(pellet);
+        let penetration = &definition.penetration;
+        let mut origin = shot.origin;
+        let mut traveled = 0.0;
+        let mut power = penetration.power;
+        let mut damage_scale = 1.0;
+
+        for layer in 0..=penetration.max_layers {
+            // Reichweite nach dem Austritt schon verbraucht
+            let remaining = definition.range - traveled;
+            if remaining <= 0.0 {
+                break;
+            }
+            let Some(hit) = spatial_query.cast_ray(origin, ray_dir, remaining, true, filter) else {
+                debug_rays.add(current_time, origin, origin + ray_dir * remaining, None, Color::srgb(1.0, 0.6, 0.6));
+                break;
+            };
+
+            let hit_point = origin + ray_dir * hit.distance;
+            traveled += hit.distance;
+            debug_rays.add(current_time, origin, hit_point, Some(hit.normal), Color::srgb(1.0, 0.2, 0.2));
+
+            // Einschlag mit Oberflächen-Eigenschaften melden (Sound, Effekte)
+            let surface = surface_query.get(hit.entity).copied().unwrap_or_default();
+            impact_events.write(SurfaceImpact {
+                entity: hit.entity,
+                point: hit_point,
+                normal: hit.normal,
+                surface,
+            });
+
+            // Treffer auf eine Hitbox zählt für deren Besitzer
+            let (target, hit_group) = damage::resolve_hit(hit.entity, hit_groups);
+            let damage = definition.damage_at(traveled) * damage_scale;
+            let force = ray_dir * damage * 0.05;
+
+            match hits.iter_mut().find(|event| event.target == target) {
+                Some(event) => {
+                    // Stärkste Hitgroup behalten, Schaden so umrechnen, dass der Multiplikator jedes Pellets erhalten bleibt
+                    let scaled = event.amount * event.hit_group.multiplier() + damage * hit_group.multiplier();
+                    if hit_group.multiplier() > event.hit_group.multiplier() {
+                        event.hit_group = hit_group;
+                    }
+                    event.amount = scaled / event.hit_group.multiplier();
+                    event.force += force;
+                }
+                None => hits.push(DamageEvent {
+                    attacker: Some(shooter),
+                    inflictor: Some(weapon_entity),
+                    hit_group,
+                    force,
+                    ..DamageEvent::new(target, damage, DamageType::Bullet, hit_point)
+                }),
+            }
+
+            if layer == penetration.max_layers || power <= 0.0 || surface.density <= 0.0 {
+                break;
+            }
+
+            // Austrittspunkt: hohler Strahl von innen trifft die Rückseite desselben Colliders
+            let max_thickness = power / surface.density;
+            let exit = spatial_query
+                .ray_hits(hit_point + ray_dir * 0.001, ray_dir, max_thickness, 8, false, filter)
+                .into_iter()
+                .filter(|exit| exit.entity == hit.entity)
+                .min_by(|a, b| a.distance.total_cmp(&b.distance));
+            let Some(exit) = exit else {
+                // Zu dick für die restliche Durchschlagskraft
+                break;
+            };
+
+            let thickness = exit.distance + 0.001;
+            let exit_point = hit_point + ray_dir * thickness;
+            power -= thickness * surface.density;
+            damage_scale *= penetration.damage_scale;
+            traveled += thickness;
+            debug_rays.add(current_time, hit_point, exit_point, None, Color::srgb(1.0, 0.8, 0.2));
+
+            impact_events.write(SurfaceImpact {
+                entity: hit.entity,
+                point: exit_point,
+                normal: ray_dir.as_vec3(),
+                surface,
+            });
+
+            origin = exit_point + ray_dir * 0.01;
+            traveled += 0.01;
         }
     }
-    
-    info!("Fired {} - Ammo remaining: {}", weapon.name, weapon.ammo);
+
+    for event in hits {
+        info!("Hit {:?} for {:.0} with {}", event.target, event.amount * event.hit_group.multiplier(), definition.name);
+        damage_events.write(event);
+    }
+
+    info!("Fired {} - {} left in magazine", definition.name, weapon.magazine);
+}
+
+/// Next swing of a melee weapon, on the weapon entity
+#[derive(Component, Debug, Clone, Copy)]
+pub struct MeleeCooldown {
+    pub ready_at: f32,
+}
+
+/// Primary and secondary swings of melee weapons: a ray first for an exact
+/// hit point, then a box sweep so glancing swings still connect like in HL
+fn weapon_melee_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    player_query: Query<(Entity, &Transform, &PlayerCmd, &PlayerInventory, &ViewPunch, Option<&Children>), (With<crate::core::fps_controller::FpsController>, Without<crate::gameplay::interaction::Carrying>, Without<crate::gameplay::interaction::AttackConsumed>, Without<WeaponSwitch>)>,
+    mut weapon_query: Query<(&mut Weapon, Option<&MeleeCooldown>)>,
+    weapon_assets: Res<Assets<WeaponDefinition>>,
+    spatial_query: SpatialQuery,
+    // Nur Lebewesen haben einen Rücken, keine Kisten
+    target_transforms: Query<&GlobalTransform, (With<damage::Health>, Without<crate::gameplay::props::Breakable>)>,
+    mut anim_player_query: Query<&mut WeaponAnimationState, With<WeaponAnimPlayer>>,
+    mut debug_rays: ResMut<crate::core::physics::DebugRays>,
+    surface_query: Query<&SurfaceProperties>,
+    mut impact_events: EventWriter<SurfaceImpact>,
+    mut damage_events: EventWriter<DamageEvent>,
+    hit_groups: Query<(&HitGroup, &ChildOf)>,
+) {
+    let current_time = time.elapsed_secs();
+
+    for (player_entity, player_transform, cmd, inventory, punch, children) in player_query.iter() {
+        let Some(weapon_entity) = inventory.held_weapon else {
+            continue;
+        };
+        let Ok((mut weapon, cooldown)) = weapon_query.get_mut(weapon_entity) else {
+            continue;
+        };
+        let Some(definition) = weapon_assets.get(&weapon.definition) else {
+            continue;
+        };
+        let Some(melee) = &definition.melee else {
+            continue;
+        };
+        if cooldown.is_some_and(|cooldown| current_time < cooldown.ready_at) {
+            continue;
+        }
+
+        let (damage, rate) = if cmd.buttons.contains(CmdButtons::ATTACK) {
+            (definition.damage, definition.fire_rate)
+        } else if cmd.buttons.contains(CmdButtons::ATTACK2) {
+            (melee.secondary_damage, melee.secondary_rate)
+        } else {
+            continue;
+        };
+
+        weapon.last_shot = current_time;
+        commands.entity(weapon_entity).insert(MeleeCooldown {
+            ready_at: current_time + rate,
+        });
+        for mut anim_state in anim_player_query.iter_mut() {
+            anim_state.firing_time = current_time;
+            anim_state.is_firing = true;
+        }
+
+        let shooter = std::iter::once(player_entity)
+            .chain(children.into_iter().flat_map(|children| children.iter()));
+        let filter = crate::core::physics::hitscan_filter().with_excluded_entities(shooter);
+
+        let origin = player_transform.translation;
+        let aim = player_transform.rotation * punch.rotation();
+        let direction = aim * Dir3::NEG_Z;
+
+        let hit = spatial_query
+            .cast_ray(origin, direction, definition.range, true, &filter)
+            .map(|hit| (hit.entity, origin + direction * hit.distance, hit.normal))
+            .or_else(|| {
+                let [x, y, z] = melee.hull;
+                spatial_query
+                    .cast_shape(
+                        &Collider::cuboid(x, y, z),
+                        origin,
+                        aim,
+                        direction,
+                        &ShapeCastConfig::from_max_distance(definition.range),
+                        &filter,
+                    )
+                    .map(|hit| (hit.entity, hit.point1, hit.normal1))
+            });
+
+        let Some((entity, point, normal)) = hit else {
+            debug_rays.add(current_time, origin, origin + direction * definition.range, None, Color::srgb(0.6, 0.6, 1.0));
+            info!("{} swing missed", definition.name);
+            continue;
+        };
+        debug_rays.add(current_time, origin, point, Some(normal), Color::srgb(0.2, 0.2, 1.0));
+
+        // Auch die Welt bekommt ihren Einschlag-Sound
+        impact_events.write(SurfaceImpact {
+            entity,
+            point,
+            normal,
+            surface: surface_query.get(entity).copied().unwrap_or_default(),
+        });
+
+        // Von hinten: Ziel schaut in dieselbe Richtung wie der Schlag
+        let (target, hit_group) = damage::resolve_hit(entity, &hit_groups);
+        let backstab = target_transforms.get(target).is_ok_and(|transform| {
+            let facing = transform.forward().with_y(0.0).normalize_or_zero();
+            facing.dot(direction.with_y(0.0).normalize_or_zero()) > 0.5
+        });
+        let amount = if backstab { damage * melee.backstab_multiplier } else { damage };
+
+        damage_events.write(DamageEvent {
+            attacker: Some(player_entity),
+            inflictor: Some(weapon_entity),
+            hit_group,
+            force: direction * amount * 0.05,
+            ..DamageEvent::new(target, amount, DamageType::Club, point)
+        });
+        info!("{} hit for {:.0}{}", definition.name, amount, if backstab { " (backstab)" } else { "" });
+    }
 }
 
 fn spawn_held_weapon_view(
     mut commands: Commands,
-    game_assets: Res<crate::content::assets::GameAssets>,
-    weapon_arm_asset: Res<crate::content::assets::WeaponArm>,
-    player_query: Query<(Entity, &PlayerInventory), (With<crate::core::fps_controller::FpsController>, Changed<PlayerInventory>)>,
+    mut graphs: ResMut<Assets<AnimationGraph>>,
+    mut definition_events: EventReader<AssetEvent<WeaponDefinition>>,
+    weapon_assets: Res<Assets<WeaponDefinition>>,
+    gltfs: Res<Assets<Gltf>>,
+    clips: Res<Assets<AnimationClip>>,
+    player_query: Query<(Entity, &PlayerInventory, Option<&WeaponSwitch>), With<crate::core::fps_controller::FpsController>>,
+    camera_query: Query<(Entity, &ChildOf), With<crate::core::fps_controller::PlayerCamera>>,
     weapon_query: Query<&Weapon>,
     existing_view_weapons: Query<Entity, With<HeldWeaponView>>,
+    // Waffe, für die zuletzt ein View-Model gebaut wurde, je Spieler
+    mut built_for: Local<HashMap<Entity, Option<Entity>>>,
 ) {
-    for (player_entity, inventory) in player_query.iter() {
+    let modified: Vec<AssetId<WeaponDefinition>> = definition_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for (player_entity, inventory, switch) in player_query.iter() {
+        let held = inventory.held_weapon.and_then(|entity| weapon_query.get(entity).ok());
+
+        // Neu aufbauen bei Waffenwechsel, nach Hot-Reload oder wenn das glTF beim Aufheben noch nicht geladen war
+        let reloaded = held.is_some_and(|weapon| modified.contains(&weapon.definition.id()));
+        let missing = held.is_some() && existing_view_weapons.is_empty();
+        let switched = built_for.get(&player_entity) != Some(&inventory.held_weapon);
+        if !switched && !reloaded && !missing {
+            continue;
+        }
+
+        // View-Model hängt an der Kamera, damit es Kamera-Bewegungen mitmacht
+        let Some((camera_entity, _)) = camera_query.iter().find(|(_, child_of)| child_of.parent() == player_entity) else {
+            continue;
+        };
+
+        let Some(weapon) = held else {
+            for entity in existing_view_weapons.iter() {
+                commands.entity(entity).despawn();
+            }
+            built_for.insert(player_entity, None);
+            continue;
+        };
+        let Some(definition) = weapon_assets.get(&weapon.definition) else {
+            continue;
+        };
+        let Some(gltf) = gltfs.get(&definition.viewmodel_gltf) else {
+            continue;
+        };
+        let Some(scene) = gltf.named_scenes.get(definition.viewmodel_scene.as_str())
+            .or(gltf.default_scene.as_ref())
+        else {
+            warn!("Viewmodel {} has no scene '{}'", definition.viewmodel, definition.viewmodel_scene);
+            continue;
+        };
+
         // Remove existing weapon view
         for entity in existing_view_weapons.iter() {
             commands.entity(entity).despawn();
         }
 
-        // Spawn new weapon view if player has a weapon
-        if let Some(weapon_entity) = inventory.held_weapon {
-            if let Ok(weapon) = weapon_query.get(weapon_entity) {
-                if game_assets.assets_loaded {
-                    let weapon_asset = weapon_arm_asset.scene.clone();
-                    // Spawn the weapon model in first-person view
-                    let weapon_view_entity = commands.spawn((
-                        SceneRoot(weapon_asset),
-                        Transform::from_translation(Vec3::new(0.2, -0.6, -0.2))
-                            .with_scale(Vec3::splat(1.0))
-                            .with_rotation(Quat::from_rotation_y(std::f32::consts::PI)),
-                        HeldWeaponView,
-                        WeaponAnimRoot,
-                        Name::new(format!("HeldWeaponView - {}", weapon.name)),
-                    )).id();
-
-                    // Make the weapon view a child of the player camera
-                    commands.entity(player_entity).add_children(&[weapon_view_entity]);
-                    
-                    info!("Spawned weapon view for {}", weapon.name);   
-                }
-            }
+        // Spawn the weapon model in first-person view
+        let view_transform = Transform::from_translation(Vec3::from(definition.viewmodel_offset))
+            .with_rotation(Quat::from_rotation_y(std::f32::consts::PI));
+        let weapon_view_entity = commands.spawn((
+            SceneRoot(scene.clone()),
+            view_transform,
+            // Sway, Bob und Landing-Kick; beim Ziehen startet die Waffe unten
+            crate::rendering::view_motion::ViewmodelMotion {
+                lower: if switch.is_some() { 1.0 } else { 0.0 },
+                ..crate::rendering::view_motion::ViewmodelMotion::new(view_transform)
+            },
+            HeldWeaponView,
+            WeaponAnimRoot,
+            build_weapon_anim_set(gltf, &definition.animations, &clips, &mut graphs),
+            Name::new(format!("HeldWeaponView - {}", definition.name)),
+        )).id();
+
+        // Make the weapon view a child of the player camera
+        commands.entity(camera_entity).add_children(&[weapon_view_entity]);
+        built_for.insert(player_entity, inventory.held_weapon);
+
+        info!("Spawned weapon view for {}", definition.name);
+    }
+}
+
+/// Animation graph with the clips named in the definition; missing clips stay `None`
+fn build_weapon_anim_set(
+    gltf: &Gltf,
+    names: &WeaponAnimationNames,
+    clips: &Assets<AnimationClip>,
+    graphs: &mut Assets<AnimationGraph>,
+) -> WeaponAnimSet {
+    let duration = |name: &str| gltf.named_animations.get(name)
+        .and_then(|clip| clips.get(clip))
+        .map(|clip| clip.duration());
+    let reload_duration = duration(&names.reload);
+    let reload_fast_duration = duration(&names.reload_fast);
+
+    let mut graph = AnimationGraph::new();
+    let root = graph.root;
+    let mut add_clip = |name: &str| match gltf.named_animations.get(name) {
+        Some(clip) => Some(graph.add_clip(clip.clone(), 1.0, root)),
+        None => {
+            warn!("Viewmodel animation '{}' not found", name);
+            None
         }
+    };
+
+    let idle = add_clip(&names.idle);
+    let walk = add_clip(&names.walk);
+    let fire = add_clip(&names.fire);
+    let reload = add_clip(&names.reload);
+    let reload_fast = add_clip(&names.reload_fast);
+
+    WeaponAnimSet {
+        graph: graphs.add(graph),
+        idle, walk, fire, reload, reload_fast,
+        reload_duration, reload_fast_duration,
     }
 }
 
@@ -401,19 +1340,23 @@ pub struct HeldWeaponView;
 
 // Utility function to add weapon inventory to player
 pub fn add_weapon_inventory_to_player(commands: &mut Commands, player_entity: Entity) {
-    commands.entity(player_entity).insert(PlayerInventory::default());
+    commands.entity(player_entity).insert((PlayerInventory::default(), Accuracy::default(), ViewPunch::default(), AimDownSights::default()));
 }
 
 fn on_scene_ready_mark_player(
     //mut ev: EventReader<SceneInstanceReady>,
-    roots: Query<Entity, (With<WeaponAnimRoot>, Without<WeaponAnimPlayer>)>,
+    roots: Query<(Entity, &WeaponAnimSet), With<WeaponAnimRoot>>,
     children_q: Query<&Children>,
     players_q: Query<Entity, With<AnimationPlayer>>,
+    marked_q: Query<(), With<WeaponAnimPlayer>>,
     mut commands: Commands,
 ) {
-    for root in &roots {
+    for (root, anims) in &roots {
         if let Some(player_entity) = find_descendant_with::<AnimationPlayer>(root, &children_q, &players_q) {
-            commands.entity(player_entity).insert(WeaponAnimPlayer);
+            if marked_q.contains(player_entity) {
+                continue;
+            }
+            commands.entity(player_entity).insert((WeaponAnimPlayer, anims.clone()));
         }
     }
 }
@@ -441,11 +1384,10 @@ fn find_descendant_with<T: Component>(
 
 fn start_idle_when_ready(
     mut cmds: Commands,
-    mut q: Query<(Entity, &mut AnimationPlayer), Added<WeaponAnimPlayer>>,
-    anims: Res<WeaponAnimSet>,
+    mut q: Query<(Entity, &mut AnimationPlayer, &WeaponAnimSet), Added<WeaponAnimPlayer>>,
 ) {
-    if let Some(idle_animation) = anims.idle {
-        for (e, mut player) in &mut q {
+    for (e, mut player, anims) in &mut q {
+        if let Some(idle_animation) = anims.idle {
             let mut transitions = AnimationTransitions::new();
             transitions.play(&mut player, idle_animation, Duration::ZERO).repeat();
 
@@ -458,7 +1400,8 @@ fn start_idle_when_ready(
     }
 }
 
-#[derive(Resource, Clone, Default)]
+/// Animation nodes of a viewmodel, on the view root and its `AnimationPlayer`
+#[derive(Component, Clone, Default)]
 pub struct WeaponAnimSet {
     pub graph: Handle<AnimationGraph>,
     pub idle: Option<AnimationNodeIndex>,
@@ -466,53 +1409,30 @@ pub struct WeaponAnimSet {
     pub walk: Option<AnimationNodeIndex>,
     pub reload: Option<AnimationNodeIndex>,
     pub reload_fast: Option<AnimationNodeIndex>,
+    /// Clip lengths in seconds, reloads finish when their animation does
+    pub reload_duration: Option<f32>,
+    pub reload_fast_duration: Option<f32>,
 }
 
 #[derive(Event)]
 pub struct WeaponAnimationChanged;
 
-// Beim Assets-Ready-Event:
-fn build_anim_graph(
-    weapon: Res<crate::content::assets::WeaponArm>, // enthält deine Clip-Handles
-    mut graphs: ResMut<Assets<AnimationGraph>>,
-    mut out: ResMut<WeaponAnimSet>,
-) {
-    // Reihenfolge festlegen -> zuordnen
-    let clip_order: Vec<Handle<AnimationClip>> = vec![
-        weapon.idle.clone(),
-        weapon.walk.clone(),
-        weapon.fire.clone(),
-        weapon.reload.clone(),
-        weapon.reload_fast.clone()
-    ];
-
-    let (graph, nodes) = AnimationGraph::from_clips(clip_order);
-    let graph_handle = graphs.add(graph);
-
-    let idle = Some(nodes[0]);
-    let walk = Some(nodes[1]);
-    let fire = Some(nodes[2]);
-    let reload = Some(nodes[3]);
-    let reload_fast = Some(nodes[4]);
-
-    *out = WeaponAnimSet { 
-        graph: graph_handle, 
-        idle, walk, fire, reload, reload_fast
-    };
-}
-
 fn update_weapon_animation_state(
     time: Res<Time>,
     keyboard: Res<ButtonInput<KeyCode>>,
     mouse_input: Res<ButtonInput<MouseButton>>,
     player_query: Query<&PlayerInventory, With<crate::core::fps_controller::FpsController>>,
-    weapon_query: Query<&Weapon>,
+    weapon_query: Query<(&Weapon, Option<&Reloading>)>,
+    weapon_assets: Res<Assets<WeaponDefinition>>,
     mut anim_player_query: Query<&mut WeaponAnimationState, With<WeaponAnimPlayer>>,
     mut animation_change_event: EventWriter<WeaponAnimationChanged>,
 ) {
     for player_inventory in player_query.iter() {
         if let Some(weapon_entity) = player_inventory.held_weapon {
-            if let Ok(weapon) = weapon_query.get(weapon_entity) {
+            let Ok((weapon, reloading)) = weapon_query.get(weapon_entity) else {
+                continue;
+            };
+            if let Some(definition) = weapon_assets.get(&weapon.definition) {
                 for mut anim_state in anim_player_query.iter_mut() {
                     // Check if player is moving
                     let is_moving = keyboard.pressed(KeyCode::KeyW) 
@@ -525,7 +1445,7 @@ fn update_weapon_animation_state(
                     
                     // Update firing state
                     let current_time = time.elapsed_secs();
-                    let is_firing = if anim_state.is_firing && (current_time - anim_state.firing_time) < weapon.fire_rate {
+                    let is_firing = if anim_state.is_firing && (current_time - anim_state.firing_time) < definition.fire_rate {
                         true
                     } else {
                         anim_state.is_firing = false;
@@ -535,6 +1455,8 @@ fn update_weapon_animation_state(
                     
                     // Determine the desired animation state
                     let desired_state = match (is_moving, is_firing) {
+                        _ if reloading.is_some_and(|reloading| reloading.kind == ReloadKind::Full) => WeaponAnimState::Reload,
+                        _ if reloading.is_some() => WeaponAnimState::ReloadFast,
                         (false, false) => WeaponAnimState::Idle,
                         (true, false) => WeaponAnimState::Walk,
                         (false, true) => WeaponAnimState::Fire,
@@ -559,13 +1481,12 @@ fn update_weapon_animation_state(
 fn handle_weapon_animation_transitions(
     mut ev: EventReader<WeaponAnimationChanged>,
     time: Res<Time>,
-    anims: Res<WeaponAnimSet>,
-    mut query: Query<(&mut AnimationPlayer, &mut AnimationTransitions, &WeaponAnimationState), With<WeaponAnimPlayer>>,
+    mut query: Query<(&mut AnimationPlayer, &mut AnimationTransitions, &WeaponAnimationState, &WeaponAnimSet), With<WeaponAnimPlayer>>,
 ) {
     if ev.is_empty() { return; }
     ev.clear(); // event wurde getriggert, also löschen damit das nicht nochmal kommt
 
-    for (mut player, mut transitions, anim_state) in query.iter_mut() {
+    for (mut player, mut transitions, anim_state, anims) in query.iter_mut() {
         // Get the animation node for the current state
         let target_animation = match anim_state.current_state {
             WeaponAnimState::Idle => anims.idle,
@@ -581,9 +1502,11 @@ fn handle_weapon_animation_transitions(
             let transition_duration = Duration::from_secs_f32(anim_state.transition_duration);
             
             info!("reset transition...");
-            transitions
-                .play(&mut player, target_node, transition_duration)
-                .repeat();
+            let animation = transitions.play(&mut player, target_node, transition_duration);
+            // Reloads laufen genau einmal, ihr Ende füllt das Magazin
+            if !matches!(anim_state.current_state, WeaponAnimState::Reload | WeaponAnimState::ReloadFast) {
+                animation.repeat();
+            }
             
             // If firing while walking, we could add fire animation as a blend
             // For now, let's keep it simple and just play the primary animation
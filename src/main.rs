@@ -13,11 +13,11 @@ use bevy_egui::EguiPlugin;
 
 // Import plugins from organized modules
 use core::{FpsControllerPlugin, PhysicsPlugin};
-use gameplay::{WeaponPlugin, InteractionPlugin, AudioPlugin};
-use content::{AssetLoadingPlugin, MapLoadingPlugin};
+use gameplay::{WeaponPlugin, InteractionPlugin, DamagePlugin, RagdollPlugin, AudioPlugin, SurfacePlugin, PropsPlugin, ExplosionPlugin, ProjectilePlugin};
+use content::{AssetLoadingPlugin, MapLoadingPlugin, WeaponDefinitionPlugin};
 use ui::{GameUIPlugin, MultiplayerPlugin};
-use rendering::LightingPlugin;
-use debug::DebugPlugin;
+use rendering::{LightingPlugin, ViewMotionPlugin, ImpactEffectsPlugin};
+use debug::{DebugPlugin, CheatsPlugin};
 use utils::GameSetupPlugin;
 
 fn main() {
@@ -35,7 +35,10 @@ fn main() {
         .add_plugins(EguiPlugin::default())
         
         // Debug tools (enhanced inspector integration)
-        .add_plugins(DebugPlugin)
+        .add_plugins((
+            DebugPlugin,
+            CheatsPlugin,
+        ))
         
         // Core game systems
         .add_plugins((
@@ -44,19 +47,30 @@ fn main() {
         ))
         
         // Rendering systems
-        .add_plugins(LightingPlugin)
+        .add_plugins((
+            LightingPlugin,
+            ViewMotionPlugin,
+            ImpactEffectsPlugin,
+        ))
         
         // Content systems
         .add_plugins((
             AssetLoadingPlugin,
             MapLoadingPlugin,
+            WeaponDefinitionPlugin,
         ))
         
         // Gameplay systems
         .add_plugins((
             WeaponPlugin,
             InteractionPlugin,
+            DamagePlugin,
+            RagdollPlugin,
             AudioPlugin,
+            SurfacePlugin,
+            PropsPlugin,
+            ExplosionPlugin,
+            ProjectilePlugin,
         ))
         
         // UI systems
@@ -0,0 +1,302 @@
+//! Bullet holes and impact particles
+//!
+//! Every [`SurfaceImpact`] leaves a decal quad lying on the hit surface with
+//! a texture per [`ImpactType`] (`assets/decals/bullet_<type>.png`) and a short
+//! burst of sparks, dust or shards. Decals come from a fixed size pool, the
+//! oldest one is recycled once the limit is reached. Decals on bodies that
+//! can move become their children and travel with them.
+
+use std::collections::{HashMap, VecDeque};
+
+use bevy::prelude::*;
+use avian3d::prelude::*;
+
+use crate::gameplay::audio::ImpactType;
+use crate::gameplay::damage::{Health, HitGroup};
+use crate::gameplay::props::Breakable;
+use crate::gameplay::surfaces::SurfaceImpact;
+
+pub struct ImpactEffectsPlugin;
+
+impl Plugin for ImpactEffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ImpactEffectSettings>()
+            .init_resource::<DecalPool>()
+            .add_systems(Startup, setup_impact_effects)
+            .add_systems(Update, (
+                spawn_impact_effects,
+                update_impact_particles,
+            ));
+    }
+}
+
+#[derive(Resource)]
+pub struct ImpactEffectSettings {
+    /// Most bullet holes alive at once
+    pub max_decals: usize,
+    /// Edge length of a bullet hole in meters
+    pub decal_size: f32,
+    /// Particles per impact, 0 disables them
+    pub particles: u32,
+}
+
+impl Default for ImpactEffectSettings {
+    fn default() -> Self {
+        Self {
+            max_decals: 128,
+            decal_size: 0.12,
+            particles: 6,
+        }
+    }
+}
+
+/// A bullet hole, owned by the [`DecalPool`]
+#[derive(Component)]
+pub struct Decal;
+
+/// Decals in spawn order, oldest first
+#[derive(Resource, Default)]
+pub struct DecalPool(VecDeque<Entity>);
+
+#[derive(Component)]
+pub struct ImpactParticle {
+    pub velocity: Vec3,
+    pub gravity: f32,
+    pub age: f32,
+    pub lifetime: f32,
+}
+
+#[derive(Resource)]
+struct ImpactEffectAssets {
+    decal_mesh: Handle<Mesh>,
+    particle_mesh: Handle<Mesh>,
+    decals: HashMap<ImpactType, Handle<StandardMaterial>>,
+    particles: HashMap<ImpactType, Handle<StandardMaterial>>,
+}
+
+/// Look and motion of the particle burst per surface type
+struct ParticleStyle {
+    color: Color,
+    /// Sparks glow, dust doesn't
+    emissive: bool,
+    speed: f32,
+    gravity: f32,
+    lifetime: f32,
+}
+
+fn particle_style(impact_type: ImpactType) -> ParticleStyle {
+    match impact_type {
+        ImpactType::Metal => ParticleStyle {
+            color: Color::srgb(1.0, 0.75, 0.3),
+            emissive: true,
+            speed: 6.0,
+            gravity: 9.81,
+            lifetime: 0.35,
+        },
+        ImpactType::Concrete => ParticleStyle {
+            color: Color::srgba(0.6, 0.58, 0.55, 0.8),
+            emissive: false,
+            speed: 2.0,
+            gravity: 2.0,
+            lifetime: 0.6,
+        },
+        ImpactType::Wood => ParticleStyle {
+            color: Color::srgb(0.55, 0.4, 0.22),
+            emissive: false,
+            speed: 3.0,
+            gravity: 9.81,
+            lifetime: 0.5,
+        },
+        ImpactType::Glass => ParticleStyle {
+            color: Color::srgba(0.8, 0.9, 1.0, 0.7),
+            emissive: false,
+            speed: 3.5,
+            gravity: 9.81,
+            lifetime: 0.6,
+        },
+        ImpactType::Soft => ParticleStyle {
+            color: Color::srgba(0.35, 0.28, 0.2, 0.8),
+            emissive: false,
+            speed: 1.5,
+            gravity: 3.0,
+            lifetime: 0.5,
+        },
+    }
+}
+
+const IMPACT_TYPES: [ImpactType; 5] = [
+    ImpactType::Metal,
+    ImpactType::Wood,
+    ImpactType::Glass,
+    ImpactType::Concrete,
+    ImpactType::Soft,
+];
+
+fn decal_texture(impact_type: ImpactType) -> &'static str {
+    match impact_type {
+        ImpactType::Metal => "decals/bullet_metal.png",
+        ImpactType::Wood => "decals/bullet_wood.png",
+        ImpactType::Glass => "decals/bullet_glass.png",
+        ImpactType::Concrete => "decals/bullet_concrete.png",
+        ImpactType::Soft => "decals/bullet_soft.png",
+    }
+}
+
+fn setup_impact_effects(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let decals = IMPACT_TYPES
+        .into_iter()
+        .map(|impact_type| {
+            let material = materials.add(StandardMaterial {
+                base_color_texture: Some(asset_server.load(decal_texture(impact_type))),
+                alpha_mode: AlphaMode::Blend,
+                perceptual_roughness: 1.0,
+                // Gegen Z-Fighting mit der Wand
+                depth_bias: 100.0,
+                ..default()
+            });
+            (impact_type, material)
+        })
+        .collect();
+
+    let particles = IMPACT_TYPES
+        .into_iter()
+        .map(|impact_type| {
+            let style = particle_style(impact_type);
+            let material = materials.add(StandardMaterial {
+                base_color: style.color,
+                emissive: if style.emissive { style.color.to_linear() * 8.0 } else { LinearRgba::BLACK },
+                alpha_mode: if style.color.alpha() < 1.0 { AlphaMode::Blend } else { AlphaMode::Opaque },
+                unlit: style.emissive,
+                ..default()
+            });
+            (impact_type, material)
+        })
+        .collect();
+
+    commands.insert_resource(ImpactEffectAssets {
+        decal_mesh: meshes.add(Rectangle::new(1.0, 1.0)),
+        particle_mesh: meshes.add(Cuboid::new(0.02, 0.02, 0.02)),
+        decals,
+        particles,
+    });
+}
+
+fn spawn_impact_effects(
+    mut commands: Commands,
+    settings: Res<ImpactEffectSettings>,
+    assets: Option<Res<ImpactEffectAssets>>,
+    mut pool: ResMut<DecalPool>,
+    mut impact_events: EventReader<SurfaceImpact>,
+    decal_query: Query<(), With<Decal>>,
+    // Keine Einschusslöcher auf Spielern und Hitboxen, Kisten dürfen welche bekommen
+    living_query: Query<(), Or<((With<Health>, Without<Breakable>), With<HitGroup>)>>,
+    body_query: Query<(&RigidBody, &GlobalTransform)>,
+    mut counter: Local<u32>,
+) {
+    let Some(assets) = assets else {
+        return;
+    };
+
+    // Löcher, die mit ihrem Eltern-Prop verschwunden sind, aus dem Pool werfen
+    pool.0.retain(|&entity| decal_query.contains(entity));
+
+    for impact in impact_events.read() {
+        let impact_type = impact.surface.impact_type;
+        let Ok(normal) = Dir3::new(impact.normal) else {
+            continue;
+        };
+
+        // --- Partikel
+        let style = particle_style(impact_type);
+        for _ in 0..settings.particles {
+            *counter = counter.wrapping_add(1);
+            let random = |salt: u32| crate::gameplay::weapons::shared_random(*counter, salt) * 2.0 - 1.0;
+            let scatter = Vec3::new(random(1), random(2), random(3)) * 0.6;
+            let speed = style.speed * (0.5 + crate::gameplay::weapons::shared_random(*counter, 4) * 0.5);
+            commands.spawn((
+                Mesh3d(assets.particle_mesh.clone()),
+                MeshMaterial3d(assets.particles[&impact_type].clone()),
+                Transform::from_translation(impact.point + normal * 0.02),
+                ImpactParticle {
+                    velocity: (normal.as_vec3() + scatter).normalize_or_zero() * speed,
+                    gravity: style.gravity,
+                    age: 0.0,
+                    lifetime: style.lifetime,
+                },
+            ));
+        }
+
+        // --- Decal
+        if living_query.contains(impact.entity) || settings.max_decals == 0 {
+            continue;
+        }
+
+        // Quad liegt knapp vor der Fläche und zeigt mit +Z entlang der Normale
+        let up = if normal.y.abs() > 0.99 { Vec3::X } else { Vec3::Y };
+        let spin = Quat::from_axis_angle(normal.as_vec3(), crate::gameplay::weapons::shared_random(*counter, 5) * std::f32::consts::TAU);
+        let world = Transform::from_translation(impact.point + normal * 0.003)
+            .looking_to(-normal.as_vec3(), up)
+            .with_scale(Vec3::splat(settings.decal_size));
+        let world = Transform { rotation: spin * world.rotation, ..world };
+
+        // Beweglichen Körpern folgen, statische Welt bleibt im Weltraum
+        let parent = body_query
+            .get(impact.entity)
+            .ok()
+            .filter(|(body, _)| **body != RigidBody::Static);
+        let transform = match parent {
+            Some((_, parent_transform)) => GlobalTransform::from(world).reparented_to(parent_transform),
+            None => world,
+        };
+
+        // Ältestes Loch recyceln, wenn das Limit erreicht ist
+        let recycled = if pool.0.len() >= settings.max_decals {
+            pool.0.pop_front().filter(|&entity| decal_query.contains(entity))
+        } else {
+            None
+        };
+        let decal = match recycled {
+            Some(entity) => {
+                commands.entity(entity).remove::<ChildOf>();
+                entity
+            }
+            None => commands.spawn((Decal, Mesh3d(assets.decal_mesh.clone()), Name::new("Decal"))).id(),
+        };
+
+        let mut decal_commands = commands.entity(decal);
+        decal_commands.insert((
+            MeshMaterial3d(assets.decals[&impact_type].clone()),
+            transform,
+        ));
+        if parent.is_some() {
+            decal_commands.insert(ChildOf(impact.entity));
+        }
+        pool.0.push_back(decal);
+    }
+}
+
+fn update_impact_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Transform, &mut ImpactParticle)>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, mut transform, mut particle) in query.iter_mut() {
+        particle.age += dt;
+        if particle.age >= particle.lifetime {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        particle.velocity.y -= particle.gravity * dt;
+        transform.translation += particle.velocity * dt;
+        transform.scale = Vec3::splat(1.0 - particle.age / particle.lifetime);
+    }
+}
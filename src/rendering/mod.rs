@@ -2,7 +2,13 @@
 //! 
 //! Contains rendering and visual systems:
 //! - Lighting and visual effects
+//! - Procedural camera and viewmodel motion
+//! - Bullet hole decals and impact particles
 
 pub mod lighting;
+pub mod view_motion;
+pub mod impact_effects;
 
-pub use lighting::LightingPlugin;
\ No newline at end of file
+pub use lighting::LightingPlugin;
+pub use view_motion::ViewMotionPlugin;
+pub use impact_effects::ImpactEffectsPlugin;
\ No newline at end of file
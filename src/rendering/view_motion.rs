@@ -0,0 +1,261 @@
+//! Procedural camera and viewmodel motion
+//!
+//! Head bob tied to ground speed, strafe roll, landing dip and recoil punch on
+//! the camera and mouse-driven sway with inertia on the held weapon. Every effect has its own
+//! scale in [`ViewMotionSettings`], 0.0 switches it off. V toggles the
+//! reduced motion preset.
+
+use bevy::prelude::*;
+use avian3d::prelude::*;
+
+use crate::core::fps_controller::{FpsController, Lean, PlayerCamera, PlayerCmd, PlayerLanded};
+use crate::gameplay::weapons::ViewPunch;
+
+pub struct ViewMotionPlugin;
+
+impl Plugin for ViewMotionPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ViewMotionSettings>()
+            .add_systems(Update, (
+                toggle_reduced_motion,
+                update_camera_motion,
+                update_viewmodel_motion,
+            ));
+    }
+}
+
+/// Strength of each procedural motion effect (1.0 = default, 0.0 = off)
+#[derive(Resource)]
+pub struct ViewMotionSettings {
+    pub view_bob: f32,
+    pub weapon_sway: f32,
+    pub strafe_roll: f32,
+    pub landing_dip: f32,
+}
+
+impl Default for ViewMotionSettings {
+    fn default() -> Self {
+        Self {
+            view_bob: 1.0,
+            weapon_sway: 1.0,
+            strafe_roll: 1.0,
+            landing_dip: 1.0,
+        }
+    }
+}
+
+impl ViewMotionSettings {
+    /// Preset for players who get motion sickness
+    pub fn reduced() -> Self {
+        Self {
+            view_bob: 0.0,
+            weapon_sway: 0.3,
+            strafe_roll: 0.0,
+            landing_dip: 0.0,
+        }
+    }
+
+    pub fn is_reduced(&self) -> bool {
+        self.view_bob == 0.0 && self.strafe_roll == 0.0 && self.landing_dip == 0.0
+    }
+}
+
+fn toggle_reduced_motion(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut settings: ResMut<ViewMotionSettings>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyV) {
+        return;
+    }
+
+    *settings = if settings.is_reduced() {
+        ViewMotionSettings::default()
+    } else {
+        ViewMotionSettings::reduced()
+    };
+    info!("Reduced view motion: {}", if settings.is_reduced() { "ON" } else { "OFF" });
+}
+
+/// Motion state of the player camera
+#[derive(Component, Default)]
+pub struct ViewMotion {
+    pub bob_phase: f32,
+    pub bob_amount: f32,
+    pub roll: f32,
+    pub dip: LandingSpring,
+}
+
+/// Motion state of a first-person weapon model, relative to its rest `base`
+#[derive(Component)]
+pub struct ViewmodelMotion {
+    pub base: Transform,
+    pub sway: Vec2,
+    pub sway_velocity: Vec2,
+    pub last_view: Option<Vec2>,
+    pub dip: LandingSpring,
+    /// Holster/draw progress, 1.0 = lowered out of view
+    pub lower: f32,
+    /// Aim-down-sights progress, 1.0 = centered in front of the eye
+    pub aim: f32,
+}
+
+impl ViewmodelMotion {
+    pub fn new(base: Transform) -> Self {
+        Self {
+            base,
+            sway: Vec2::ZERO,
+            sway_velocity: Vec2::ZERO,
+            last_view: None,
+            dip: LandingSpring::default(),
+            lower: 0.0,
+            aim: 0.0,
+        }
+    }
+}
+
+/// Critically damped spring that pushes down on landing and settles back to 0
+#[derive(Default, Clone, Copy)]
+pub struct LandingSpring {
+    pub offset: f32,
+    pub velocity: f32,
+}
+
+impl LandingSpring {
+    const STIFFNESS: f32 = 120.0;
+
+    pub fn kick(&mut self, impact_speed: f32) {
+        self.velocity += impact_speed * 0.04;
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        let damping = 2.0 * Self::STIFFNESS.sqrt();
+        let accel = -Self::STIFFNESS * self.offset - damping * self.velocity;
+        self.velocity += accel * dt;
+        self.offset = (self.offset + self.velocity * dt).clamp(0.0, 0.5);
+    }
+}
+
+// HL-Werte (cl_bob, cl_rollangle, cl_rollspeed) auf Meter umgerechnet
+const BOB_HEIGHT: f32 = 0.04;
+const BOB_CYCLE: f32 = 2.2;       // Schritte pro Meter * 2π grob angenähert
+const ROLL_ANGLE_DEG: f32 = 2.0;
+const ROLL_SPEED: f32 = 5.0;
+const HOLSTER_DROP: f32 = 0.35;    // Meter, um die das View-Model beim Wechsel absinkt
+
+fn update_camera_motion(
+    time: Res<Time>,
+    settings: Res<ViewMotionSettings>,
+    mut landed_events: EventReader<PlayerLanded>,
+    player_query: Query<(&FpsController, &PlayerCmd, &LinearVelocity, Option<&Lean>, Option<&ViewPunch>)>,
+    mut camera_query: Query<(&mut Transform, &mut ViewMotion, &ChildOf), With<PlayerCamera>>,
+) {
+    let dt = time.delta_secs();
+    let landings: Vec<PlayerLanded> = landed_events.read().copied().collect();
+
+    for (mut transform, mut motion, child_of) in camera_query.iter_mut() {
+        let Ok((controller, cmd, velocity, lean, punch)) = player_query.get(child_of.parent()) else {
+            continue;
+        };
+
+        let ground_velocity = Vec3::new(velocity.x, 0.0, velocity.z);
+        let ground_speed = ground_velocity.length();
+
+        // --- Head bob: nur am Boden, Amplitude folgt der Geschwindigkeit
+        let target_amount = if controller.is_grounded {
+            (ground_speed / controller.speed.max(0.1)).min(1.0)
+        } else {
+            0.0
+        };
+        motion.bob_amount += (target_amount - motion.bob_amount) * (dt * 8.0).min(1.0);
+        motion.bob_phase = (motion.bob_phase + ground_speed * BOB_CYCLE * dt) % std::f32::consts::TAU;
+
+        // --- Strafe roll wie V_CalcRoll
+        let right = Quat::from_rotation_y(cmd.yaw) * Vec3::X;
+        let side = ground_velocity.dot(right);
+        let target_roll = -(side / ROLL_SPEED).clamp(-1.0, 1.0) * ROLL_ANGLE_DEG.to_radians();
+        motion.roll += (target_roll - motion.roll) * (dt * 10.0).min(1.0);
+
+        // --- Landing dip
+        for landed in landings.iter().filter(|landed| landed.entity == child_of.parent()) {
+            motion.dip.kick(landed.speed);
+        }
+        motion.dip.update(dt);
+
+        let bob = motion.bob_amount * BOB_HEIGHT * settings.view_bob;
+        let offset = Vec3::new(
+            motion.bob_phase.cos() * bob * 0.5,
+            motion.bob_phase.sin().abs() * bob - motion.dip.offset * settings.landing_dip,
+            0.0,
+        );
+
+        // Lean und Punch sind keine Komfort-Effekte und werden immer angewendet
+        let lean = lean.map(Lean::view_offset).unwrap_or_default();
+        let punch = punch.map_or(Quat::IDENTITY, ViewPunch::rotation);
+        transform.translation = lean.translation + offset;
+        transform.rotation = lean.rotation * punch * Quat::from_rotation_z(motion.roll * settings.strafe_roll);
+    }
+}
+
+fn update_viewmodel_motion(
+    time: Res<Time>,
+    settings: Res<ViewMotionSettings>,
+    mut landed_events: EventReader<PlayerLanded>,
+    player_query: Query<(&FpsController, &PlayerCmd, &LinearVelocity)>,
+    camera_query: Query<&ViewMotion, With<PlayerCamera>>,
+    mut viewmodel_query: Query<(&mut Transform, &mut ViewmodelMotion)>,
+) {
+    let dt = time.delta_secs();
+    if dt <= 0.0 {
+        return;
+    }
+
+    let Ok((controller, cmd, velocity)) = player_query.single() else {
+        return;
+    };
+    let bob_phase = camera_query.iter().next().map_or(0.0, |motion| motion.bob_phase);
+    let landing_speed = landed_events.read().map(|landed| landed.speed).fold(0.0, f32::max);
+
+    for (mut transform, mut motion) in viewmodel_query.iter_mut() {
+        // --- Sway: Waffe hängt der Mausbewegung hinterher
+        let view = Vec2::new(cmd.yaw, cmd.pitch);
+        let view_delta = motion.last_view.map_or(Vec2::ZERO, |last| view - last);
+        motion.last_view = Some(view);
+
+        let target = (-view_delta / dt * 0.01).clamp(Vec2::splat(-0.06), Vec2::splat(0.06));
+        let stiffness: f32 = 90.0;
+        let damping = 2.0 * stiffness.sqrt() * 0.8; // leicht unterdämpft für etwas Nachschwingen
+        let accel = (target - motion.sway) * stiffness - motion.sway_velocity * damping;
+        motion.sway_velocity += accel * dt;
+        let sway_velocity = motion.sway_velocity;
+        motion.sway += sway_velocity * dt;
+
+        // --- Landing kick
+        if landing_speed > 0.0 {
+            motion.dip.kick(landing_speed * 0.5);
+        }
+        motion.dip.update(dt);
+
+        // --- Bob: Waffe bewegt sich leicht gegen den Kopf
+        let ground_speed = Vec2::new(velocity.x, velocity.z).length();
+        let bob = if controller.is_grounded {
+            (ground_speed / controller.speed.max(0.1)).min(1.0) * 0.015 * settings.view_bob
+        } else {
+            0.0
+        };
+
+        // Beim Zielen wandert die Waffe zur Mitte und wackelt kaum noch
+        let steady = 1.0 - motion.aim * 0.8;
+        let sway = motion.sway * settings.weapon_sway * steady;
+        let bob = bob * steady;
+        let offset = Vec3::new(
+            sway.x + bob_phase.cos() * bob - motion.base.translation.x * motion.aim,
+            sway.y - bob_phase.sin().abs() * bob - motion.dip.offset * settings.landing_dip - motion.lower * HOLSTER_DROP,
+            0.0,
+        );
+
+        transform.translation = motion.base.translation + offset;
+        transform.rotation = motion.base.rotation
+            * Quat::from_euler(EulerRot::YXZ, sway.x * 2.0, sway.y * 2.0, -sway.x * 3.0);
+    }
+}
@@ -110,7 +110,7 @@ fn disconnect(network_settings: &mut NetworkSettings) {
 pub enum NetworkMessage {
     PlayerJoined { player_id: u32 },
     PlayerLeft { player_id: u32 },
-    PlayerMove { player_id: u32, transform: Transform },
+    PlayerMove { player_id: u32, transform: Transform, lean: f32 },
     Chat { player_id: u32, message: String },
 }
 
@@ -134,11 +134,12 @@ pub fn spawn_network_player(
 pub fn send_player_update(
     player_id: u32,
     transform: Transform,
+    lean: f32,
     network_settings: &NetworkSettings,
 ) {
     if network_settings.is_client || network_settings.is_server {
         // TODO: Send transform update over network
-        info!("Sending player {} update: {:?}", player_id, transform.translation);
+        info!("Sending player {} update: {:?} (lean {:.2})", player_id, transform.translation, lean);
     }
 }
 
@@ -156,12 +157,15 @@ pub fn interpolate_network_players(
 }
 
 pub fn send_local_player_updates(
-    query: Query<(Entity, &Transform, &NetworkPlayer)>,
+    query: Query<(Entity, &Transform, &NetworkPlayer, Option<&crate::core::fps_controller::Lean>)>,
     network_settings: Res<NetworkSettings>,
 ) {
-    for (_, transform, player) in query.iter() {
+    for (_, transform, player, lean) in query.iter() {
         if player.is_local {
-            send_player_update(player.player_id, *transform, &network_settings);
+            // Gesendet wird die geneigte Kopfposition, damit andere den Lean sehen
+            let lean = lean.copied().unwrap_or_default();
+            let transform = transform.mul_transform(lean.view_offset());
+            send_player_update(player.player_id, transform, lean.amount, &network_settings);
         }
     }
 }
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
 
 pub struct GameUIPlugin;
 
@@ -9,9 +10,11 @@ impl Plugin for GameUIPlugin {
             .add_systems(Update, (
                 update_fps_display,
                 update_crosshair,
+                update_scope_overlay,
                 handle_ui_input,
                 update_debug_info,
                 update_health_display,
+                update_armor_display,
                 update_ammo_display,
                 update_weapon_display,
                 update_loading_screen,
@@ -26,12 +29,22 @@ pub struct FpsText;
 #[derive(Component)]
 pub struct Crosshair;
 
+/// Crosshair box size in pixels at zero spread
+const CROSSHAIR_SIZE: f32 = 20.0;
+
+/// Black scope mask, shown while zoomed through a scope
+#[derive(Component)]
+pub struct ScopeOverlay;
+
 #[derive(Component)]
 pub struct DebugInfo;
 
 #[derive(Component)]
 pub struct HealthDisplay;
 
+#[derive(Component)]
+pub struct ArmorDisplay;
+
 #[derive(Component)]
 pub struct AmmoDisplay;
 
@@ -75,15 +88,78 @@ fn setup_ui(mut commands: Commands) {
             ..default()
         })
         .with_children(|parent| {
+            // Scope overlay: schwarze Ränder um einen runden Ausschnitt mit Fadenkreuz
+            parent.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Row,
+                    display: Display::None,
+                    ..default()
+                },
+                ScopeOverlay,
+            ))
+            .with_children(|scope| {
+                scope.spawn((
+                    Node {
+                        flex_grow: 1.0,
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::BLACK),
+                ));
+                scope.spawn((
+                    Node {
+                        height: Val::Percent(100.0),
+                        aspect_ratio: Some(1.0),
+                        border: UiRect::all(Val::Px(4.0)),
+                        ..default()
+                    },
+                    BorderColor(Color::BLACK),
+                    BorderRadius::MAX,
+                ))
+                .with_children(|lens| {
+                    lens.spawn((
+                        Node {
+                            position_type: PositionType::Absolute,
+                            width: Val::Percent(100.0),
+                            height: Val::Px(2.0),
+                            top: Val::Percent(50.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::BLACK),
+                    ));
+                    lens.spawn((
+                        Node {
+                            position_type: PositionType::Absolute,
+                            width: Val::Px(2.0),
+                            height: Val::Percent(100.0),
+                            left: Val::Percent(50.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::BLACK),
+                    ));
+                });
+                scope.spawn((
+                    Node {
+                        flex_grow: 1.0,
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::BLACK),
+                ));
+            });
+
             // Crosshair (center)
             parent.spawn((
                 Node {
                     position_type: PositionType::Absolute,
-                    width: Val::Px(20.0),
-                    height: Val::Px(20.0),
+                    width: Val::Px(CROSSHAIR_SIZE),
+                    height: Val::Px(CROSSHAIR_SIZE),
                     left: Val::Percent(50.0),
                     top: Val::Percent(50.0),
-                    margin: UiRect::new(Val::Px(-10.0), Val::Px(0.0), Val::Px(-10.0), Val::Px(0.0)),
+                    margin: UiRect::new(Val::Px(-CROSSHAIR_SIZE / 2.0), Val::Px(0.0), Val::Px(-CROSSHAIR_SIZE / 2.0), Val::Px(0.0)),
                     border: UiRect::all(Val::Px(1.0)),
                     ..default()
                 },
@@ -98,8 +174,9 @@ fn setup_ui(mut commands: Commands) {
                         position_type: PositionType::Absolute,
                         width: Val::Px(12.0),
                         height: Val::Px(2.0),
-                        left: Val::Px(4.0),
-                        top: Val::Px(9.0),
+                        left: Val::Percent(50.0),
+                        top: Val::Percent(50.0),
+                        margin: UiRect::new(Val::Px(-6.0), Val::Px(0.0), Val::Px(-1.0), Val::Px(0.0)),
                         ..default()
                     },
                     BackgroundColor(Color::srgb(1.0, 1.0, 1.0)),
@@ -111,8 +188,9 @@ fn setup_ui(mut commands: Commands) {
                         position_type: PositionType::Absolute,
                         width: Val::Px(2.0),
                         height: Val::Px(12.0),
-                        left: Val::Px(9.0),
-                        top: Val::Px(4.0),
+                        left: Val::Percent(50.0),
+                        top: Val::Percent(50.0),
+                        margin: UiRect::new(Val::Px(-1.0), Val::Px(0.0), Val::Px(-6.0), Val::Px(0.0)),
                         ..default()
                     },
                     BackgroundColor(Color::srgb(1.0, 1.0, 1.0)),
@@ -136,9 +214,26 @@ fn setup_ui(mut commands: Commands) {
                 HealthDisplay,
             ));
 
+            // Armor display (next to health)
+            parent.spawn((
+                Text::new("Armor: 0"),
+                Node {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(20.0),
+                    left: Val::Px(230.0),
+                    ..default()
+                },
+                TextColor(Color::srgb(0.3, 0.5, 0.9)),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+                ArmorDisplay,
+            ));
+
             // Ammo display (bottom right)
             parent.spawn((
-                Text::new("Ammo: -/-"),
+                Text::new("Ammo: - / -"),
                 Node {
                     position_type: PositionType::Absolute,
                     bottom: Val::Px(20.0),
@@ -182,13 +277,41 @@ fn update_fps_display(
 fn update_crosshair(
     mut query: Query<&mut Node, With<Crosshair>>,
     ui_settings: Res<UISettings>,
+    player_query: Query<(&crate::gameplay::weapons::Accuracy, Option<&crate::gameplay::weapons::AimDownSights>), With<crate::core::fps_controller::FpsController>>,
+    camera_query: Query<&Projection, With<crate::core::fps_controller::PlayerCamera>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
 ) {
+    // Spread-Kegel auf Bildschirm-Pixel umrechnen, damit das Fadenkreuz die echte Streuung zeigt
+    let spread = player_query.iter().next().map_or(0.0, |(accuracy, _)| accuracy.spread);
+    // Im Scope hat das Overlay sein eigenes Fadenkreuz
+    let scoped = player_query.iter().next().is_some_and(|(_, aim)| aim.is_some_and(|aim| aim.scoped));
+    let fov = match camera_query.iter().next() {
+        Some(Projection::Perspective(perspective)) => perspective.fov,
+        _ => std::f32::consts::FRAC_PI_4,
+    };
+    let screen_height = window_query.single().map_or(720.0, |window| window.height());
+    let gap = spread.to_radians().tan() / (fov * 0.5).tan() * screen_height * 0.5;
+    let size = CROSSHAIR_SIZE + gap * 2.0;
+
     for mut style in &mut query {
-        style.display = if ui_settings.show_crosshair {
+        style.display = if ui_settings.show_crosshair && !scoped {
             Display::Flex
         } else {
             Display::None
         };
+        style.width = Val::Px(size);
+        style.height = Val::Px(size);
+        style.margin = UiRect::new(Val::Px(-size / 2.0), Val::Px(0.0), Val::Px(-size / 2.0), Val::Px(0.0));
+    }
+}
+
+fn update_scope_overlay(
+    player_query: Query<&crate::gameplay::weapons::AimDownSights, With<crate::core::fps_controller::FpsController>>,
+    mut overlay_query: Query<&mut Node, With<ScopeOverlay>>,
+) {
+    let scoped = player_query.iter().next().is_some_and(|aim| aim.scoped);
+    for mut style in &mut overlay_query {
+        style.display = if scoped { Display::Flex } else { Display::None };
     }
 }
 
@@ -212,7 +335,7 @@ fn handle_ui_input(
 }
 
 fn update_health_display(
-    player_query: Query<&crate::gameplay::interaction::PlayerHealth, With<crate::core::fps_controller::FpsController>>,
+    player_query: Query<&crate::gameplay::damage::Health, With<crate::core::fps_controller::FpsController>>,
     mut health_display_query: Query<&mut Text, With<HealthDisplay>>,
 ) {
     for player_health in player_query.iter() {
@@ -222,21 +345,45 @@ fn update_health_display(
     }
 }
 
+fn update_armor_display(
+    player_query: Query<&crate::gameplay::damage::Armor, With<crate::core::fps_controller::FpsController>>,
+    mut armor_display_query: Query<&mut Text, With<ArmorDisplay>>,
+) {
+    for armor in player_query.iter() {
+        for mut text in armor_display_query.iter_mut() {
+            text.0 = format!("Armor: {:.0}", armor.current);
+        }
+    }
+}
+
 fn update_ammo_display(
     player_query: Query<&crate::gameplay::weapons::PlayerInventory, With<crate::core::fps_controller::FpsController>>,
     weapon_query: Query<&crate::gameplay::weapons::Weapon>,
+    weapon_assets: Res<Assets<crate::content::weapon_definitions::WeaponDefinition>>,
     mut ammo_display_query: Query<&mut Text, With<AmmoDisplay>>,
 ) {
     for inventory in player_query.iter() {
         for mut text in ammo_display_query.iter_mut() {
             if let Some(weapon_entity) = inventory.held_weapon {
-                if let Ok(weapon) = weapon_query.get(weapon_entity) {
-                    text.0 = format!("Ammo: {}/{}", weapon.ammo, weapon.max_ammo);
+                // Waffen ohne Munition (Nahkampf) zeigen nichts an
+                let weapon = weapon_query.get(weapon_entity).ok()
+                    .and_then(|weapon| {
+                        let definition = weapon_assets.get(&weapon.definition)?;
+                        Some((weapon, definition, definition.ammo_type?))
+                    });
+                if let Some((weapon, definition, ammo_type)) = weapon {
+                    text.0 = format!("Ammo: {} / {}", weapon.magazine, inventory.ammo(ammo_type));
+                    // Feuermodus nur zeigen, wenn man umschalten kann
+                    if definition.fire_modes.len() > 1 {
+                        if let Some(mode) = definition.fire_modes.get(weapon.fire_mode) {
+                            text.0 += &format!(" [{}]", mode.name());
+                        }
+                    }
                 } else {
-                    text.0 = "Ammo: -/-".to_string();
+                    text.0 = "Ammo: - / -".to_string();
                 }
             } else {
-                text.0 = "Ammo: -/-".to_string();
+                text.0 = "Ammo: - / -".to_string();
             }
         }
     }
@@ -245,13 +392,16 @@ fn update_ammo_display(
 fn update_weapon_display(
     player_query: Query<&crate::gameplay::weapons::PlayerInventory, With<crate::core::fps_controller::FpsController>>,
     weapon_query: Query<&crate::gameplay::weapons::Weapon>,
+    weapon_assets: Res<Assets<crate::content::weapon_definitions::WeaponDefinition>>,
     mut weapon_display_query: Query<&mut Text, With<WeaponDisplay>>,
 ) {
     for inventory in player_query.iter() {
         for mut text in weapon_display_query.iter_mut() {
             if let Some(weapon_entity) = inventory.held_weapon {
-                if let Ok(weapon) = weapon_query.get(weapon_entity) {
-                    text.0 = weapon.name.clone();
+                let definition = weapon_query.get(weapon_entity).ok()
+                    .and_then(|weapon| weapon_assets.get(&weapon.definition));
+                if let Some(definition) = definition {
+                    text.0 = definition.name.clone();
                 } else {
                     text.0 = "No Weapon".to_string();
                 }
@@ -36,7 +36,7 @@ fn setup_game(
     for i in 0..3 {
         let position = Vec3::new(
             (i as f32 - 1.0) * 8.0, 
-            0.1, 
+            1.1, 
             -3.0 + (i as f32 * 2.0)
         );
         assets::spawn_character_model(&mut commands, &mut meshes, &mut materials, position);